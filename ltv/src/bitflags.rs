@@ -0,0 +1,118 @@
+use std::marker::PhantomData;
+
+use ::bitflags::Flags;
+
+use crate::{
+    error::{LTVError, LTVResult},
+    ByteOrder, LTVItem,
+};
+
+/// What to do with bits that are not defined by the flags type when decoding.
+pub trait UnknownBits {
+    /// `None` if the bits are rejected.
+    fn from_bits<T: Flags>(bits: T::Bits) -> Option<T>;
+}
+
+/// Keep unknown bits, so they are written back unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Retain;
+
+/// Drop unknown bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Truncate;
+
+/// Fail with `LTVError::UnexpectedValue` on unknown bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Reject;
+
+impl UnknownBits for Retain {
+    fn from_bits<T: Flags>(bits: T::Bits) -> Option<T> {
+        Some(T::from_bits_retain(bits))
+    }
+}
+
+impl UnknownBits for Truncate {
+    fn from_bits<T: Flags>(bits: T::Bits) -> Option<T> {
+        Some(T::from_bits_truncate(bits))
+    }
+}
+
+impl UnknownBits for Reject {
+    fn from_bits<T: Flags>(bits: T::Bits) -> Option<T> {
+        T::from_bits(bits)
+    }
+}
+
+/// A `bitflags` type written as its underlying integer in the field byte order,
+/// with `P` deciding what happens to unknown bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BitFlags<T, P = Retain>(pub T, pub PhantomData<P>);
+
+impl<T, P> BitFlags<T, P> {
+    pub fn new(flags: T) -> Self {
+        Self(flags, PhantomData)
+    }
+}
+
+impl<T, P> From<T> for BitFlags<T, P> {
+    fn from(flags: T) -> Self {
+        Self::new(flags)
+    }
+}
+
+impl<T, P, const ED: ByteOrder> LTVItem<ED> for BitFlags<T, P>
+where
+    T: Flags,
+    T::Bits: LTVItem<ED> + std::fmt::Debug,
+    P: UnknownBits,
+{
+    const FIXED_SIZE: Option<usize> = <T::Bits as LTVItem<ED>>::FIXED_SIZE;
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        let bits = <T::Bits as LTVItem<ED>>::from_ltv(field_id, data)?;
+        match P::from_bits::<T>(bits) {
+            Some(flags) => Ok(Self::new(flags)),
+            None => Err(LTVError::UnexpectedValue(
+                field_id,
+                format!("unknown flags in {:?}", bits),
+            )),
+        }
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.0.bits().to_ltv()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        self.0.bits().encode_ltv(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    ::bitflags::bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct Perms: u16 {
+            const READ = 0x01;
+            const WRITE = 0x02;
+        }
+    }
+
+    #[test]
+    fn bit_flags() {
+        let perms = BitFlags::<Perms>::new(Perms::READ | Perms::WRITE);
+        assert_eq!(<BitFlags<Perms> as LTVItem<{ ByteOrder::LE }>>::to_ltv(&perms), vec![0x03, 0x00]);
+
+        let data = [0x00, 0x81];
+        let retained = <BitFlags<Perms> as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap();
+        assert_eq!(retained.0.bits(), 0x81);
+        let truncated = <BitFlags<Perms, Truncate> as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap();
+        assert_eq!(truncated.0, Perms::READ);
+        assert!(matches!(
+            <BitFlags<Perms, Reject> as LTVItem<{ ByteOrder::BE }>>::from_ltv(5, &data),
+            Err(LTVError::UnexpectedValue(5, _))
+        ));
+    }
+}
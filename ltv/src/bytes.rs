@@ -0,0 +1,224 @@
+use ::bytes::{BufMut, Bytes, BytesMut};
+
+use crate::{
+    error::{LTVError, LTVResult},
+    object::copy_to_buffer,
+    reader::LTVReader,
+    writer::encode_header,
+    ByteOrder, LTVContainer, LTVItem, LTVItemMany, LTVItemMap,
+};
+
+/// `LTVContainer` for any `BufMut` (e.g. `BytesMut`).
+pub struct LTVBufMut<B: BufMut>(pub B);
+
+impl<B: BufMut> LTVBufMut<B> {
+    pub fn new(buf: B) -> Self {
+        Self(buf)
+    }
+
+    pub fn into_inner(self) -> B {
+        self.0
+    }
+}
+
+impl<B: BufMut, const ED: ByteOrder, const LENGTH_SIZE: usize> LTVContainer<ED, LENGTH_SIZE>
+    for LTVBufMut<B>
+{
+    fn write_ltv<'a, T: LTVItem<ED>>(&mut self, obj_id: u8, obj: &T) -> LTVResult<usize> {
+        if let Some(data) = obj.as_ltv_bytes() {
+            if data.is_empty() {
+                return Ok(0);
+            }
+            return <Self as LTVContainer<ED, LENGTH_SIZE>>::write_ltv_data(self, obj_id, data);
+        }
+
+        let data = obj.try_to_ltv()?;
+        if data.is_empty() {
+            return Ok(0);
+        }
+        <Self as LTVContainer<ED, LENGTH_SIZE>>::write_ltv_data(self, obj_id, &data)
+    }
+
+    fn write_ltv_data(&mut self, obj_id: u8, data: &[u8]) -> LTVResult<usize> {
+        let (header, header_size) = encode_header::<ED, LENGTH_SIZE>(obj_id, data.len())?;
+
+        let size = header_size + data.len();
+        if self.0.remaining_mut() < size {
+            return Err(LTVError::BufferTooSmall {
+                required: size,
+                available: self.0.remaining_mut(),
+            });
+        }
+        self.0.put_slice(&header[..header_size]);
+        self.0.put_slice(data);
+        Ok(size)
+    }
+}
+
+/// Reader over a `Bytes` body.
+///
+/// Values are handed out as slices of the original buffer,
+/// so `Bytes` fields (and `Option<Bytes>`) are decoded without copying,
+/// including the ones nested in derived structs.
+pub struct LTVBytesReader<const ED: ByteOrder, const LENGTH_SIZE: usize> {
+    body: Bytes,
+}
+
+impl<const ED: ByteOrder, const LENGTH_SIZE: usize> LTVBytesReader<ED, LENGTH_SIZE> {
+    pub fn new(body: Bytes) -> Self {
+        Self { body }
+    }
+
+    /// Iterates over every field as `(field_id, value)`.
+    pub fn fields(&self) -> impl Iterator<Item = LTVResult<(u8, Bytes)>> + '_ {
+        let mut i = 0;
+        std::iter::from_fn(move || {
+            if i >= self.body.len() {
+                return None;
+            }
+            match LTVReader::<ED, LENGTH_SIZE>::parse_ltv(&self.body[i..]) {
+                Ok((length, field_id, data)) => {
+                    let value_start = i + length - data.len();
+                    let value = self.body.slice(value_start..i + length);
+                    i += length;
+                    Some(Ok((field_id, value)))
+                }
+                Err(e) => {
+                    i = self.body.len();
+                    Some(Err(e))
+                }
+            }
+        })
+    }
+
+    /// The raw value of the first field with `field_id`.
+    pub fn get_bytes(&self, field_id: u8) -> LTVResult<Option<Bytes>> {
+        for field in self.fields() {
+            let (id, value) = field?;
+            if id == field_id {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn get_item_optional<T: LTVItem<ED>>(&self, field_id: u8) -> LTVResult<Option<T>> {
+        match self.get_bytes(field_id)? {
+            Some(value) => Ok(Some(T::from_ltv_bytes(field_id, value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_item<T: LTVItem<ED>>(&self, field_id: u8) -> LTVResult<T> {
+        match self.get_item_optional::<T>(field_id)? {
+            Some(o) => Ok(o),
+            None => T::not_found(field_id),
+        }
+    }
+
+    pub fn get_many<T: LTVItem<ED>, M: LTVItemMany<ED>>(&self, field_id: u8) -> LTVResult<M> {
+        let items = self.fields().filter_map(|field| match field {
+            Ok((id, value)) if id == field_id => Some(M::Item::from_ltv_bytes(field_id, value)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        });
+        M::from_items(field_id, items)
+    }
+
+    /// Reads every `field_id` field as a map entry, see `LTVReader::get_map`.
+    pub fn get_map<M: LTVItemMap<ED>>(&self, field_id: u8, key_id: u8, value_id: u8) -> LTVResult<M> {
+        let mut map = M::new();
+        for field in self.fields() {
+            let (id, value) = field?;
+            if id == field_id {
+                let entry = Self::new(value);
+                let key = entry.get_item::<M::Key>(key_id)?;
+                let value = entry.get_item::<M::Value>(value_id)?;
+                if !map.insert_entry(key, value) {
+                    return Err(LTVError::DuplicateKey(field_id));
+                }
+            }
+        }
+        Ok(map)
+    }
+
+    pub fn into_inner(self) -> Bytes {
+        self.body
+    }
+}
+
+impl<const ED: ByteOrder> LTVItem<ED> for Bytes {
+    fn from_ltv(_field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        Ok(Bytes::copy_from_slice(data))
+    }
+
+    fn from_ltv_bytes(_field_id: u8, data: Bytes) -> LTVResult<Self> {
+        Ok(data)
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(self, buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
+
+impl<const ED: ByteOrder> LTVItem<ED> for BytesMut {
+    fn from_ltv(_field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        Ok(BytesMut::from(data))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(self, buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::bytes::{Bytes, BytesMut};
+
+    use crate::*;
+
+    #[test]
+    fn buf_mut_to_bytes_reader() {
+        let mut writer = LTVBufMut::new(BytesMut::new());
+        LTVContainer::<{ ByteOrder::BE }, 1>::write_ltv(&mut writer, 0x01, &0x35u8).unwrap();
+        LTVContainer::<{ ByteOrder::BE }, 1>::write_ltv(&mut writer, 0x02, &Bytes::from_static(b"hello")).unwrap();
+        let body = writer.into_inner().freeze();
+        assert_eq!(&body[..], &[2, 0x01, 0x35, 6, 0x02, b'h', b'e', b'l', b'l', b'o']);
+
+        let reader = LTVBytesReaderBE::<1>::new(body.clone());
+        assert_eq!(reader.get_item::<u8>(0x01).unwrap(), 0x35);
+
+        let value = reader.get_item::<Bytes>(0x02).unwrap();
+        assert_eq!(&value[..], b"hello");
+        // the value points into the original buffer
+        assert_eq!(value.as_ptr(), body[5..].as_ptr());
+
+        assert_eq!(reader.get_item::<Option<Bytes>>(0x03).unwrap(), None);
+    }
+
+    #[test]
+    fn buf_mut_too_small() {
+        let mut buf = [0u8; 2];
+        let mut writer = LTVBufMut::new(&mut buf[..]);
+        assert!(matches!(
+            LTVContainer::<{ ByteOrder::BE }, 1>::write_ltv(&mut writer, 0x01, &0x1234u16),
+            Err(LTVError::BufferTooSmall { required: 4, available: 2 })
+        ));
+    }
+}
@@ -9,7 +9,56 @@ pub enum LTVError {
     },
     NotFound(u8),
     UnexpectedValue(u8, String),
-    InnerParseError(Box<LTVError>, String)
+    InnerParseError(Box<LTVError>, String),
+    /// A `bool` value that is not 0 or 1.
+    InvalidBool {
+        field_id: u8,
+        value: u8,
+    },
+    /// A `char` value that is not a unicode scalar value.
+    InvalidChar {
+        field_id: u8,
+        value: u32,
+    },
+    InvalidUtf8 {
+        field_id: u8,
+        error: std::str::Utf8Error,
+    },
+    /// Text that is not valid for its encoding (NUL terminated, UTF-16, ...).
+    InvalidText(u8, String),
+    /// A map or set field with the same key in more than one entry.
+    DuplicateKey(u8),
+    /// A fixed capacity collection field has more than `capacity` items.
+    CapacityOverflow {
+        field_id: u8,
+        capacity: usize,
+    },
+    /// A LEB128 varint that is truncated, overlong or too large for its type.
+    InvalidVarInt(u8, String),
+    /// A one-of value with `found` of its fields present instead of exactly one.
+    OneOf {
+        field_id: u8,
+        found: usize,
+    },
+    /// A value that can not be represented on the wire.
+    EncodeError(String),
+    /// The value of `field_id` is too long to fit in the length header.
+    LengthOverflow {
+        field_id: u8,
+        length: usize,
+        max: usize,
+    },
+    /// A canonical writer was given `field_id` after the larger tag `previous`.
+    UnorderedField {
+        field_id: u8,
+        previous: u8,
+    },
+    /// The output buffer is too small, `required` is the total number of bytes needed.
+    BufferTooSmall {
+        required: usize,
+        available: usize,
+    },
+    Io(std::io::Error),
 }
 
 impl Error for LTVError {}
@@ -20,4 +69,10 @@ impl std::fmt::Display for LTVError {
     }
 }
 
+impl From<std::io::Error> for LTVError {
+    fn from(e: std::io::Error) -> Self {
+        LTVError::Io(e)
+    }
+}
+
 pub type LTVResult<T> = std::result::Result<T, LTVError>;
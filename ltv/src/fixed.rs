@@ -0,0 +1,102 @@
+use crate::{
+    error::{LTVError, LTVResult},
+    ByteOrder, LTVItem,
+};
+
+/// A decimal number written as the integer `W` scaled by `10^DECIMALS`,
+/// e.g. `Fixed<i16, 2>` writes `21.37` as `2137i16`.
+///
+/// Encoding fails with `LTVError::EncodeError` if the value is not finite,
+/// does not fit in `W` or has more than `DECIMALS` decimal places.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Fixed<W, const DECIMALS: u32, V = f64>(pub V, pub std::marker::PhantomData<W>);
+
+impl<W, const DECIMALS: u32, V> Fixed<W, DECIMALS, V> {
+    pub fn new(value: V) -> Self {
+        Self(value, std::marker::PhantomData)
+    }
+
+    pub fn get(self) -> V {
+        self.0
+    }
+}
+
+impl<W, const DECIMALS: u32, V> From<V> for Fixed<W, DECIMALS, V> {
+    fn from(value: V) -> Self {
+        Self::new(value)
+    }
+}
+
+/// `10^DECIMALS` as a float.
+fn scale(decimals: u32) -> f64 {
+    10f64.powi(decimals as i32)
+}
+
+/// Scales `value` to the nearest integer, failing if that loses more than float rounding error.
+fn to_units(value: f64, decimals: u32, epsilon: f64) -> LTVResult<f64> {
+    if !value.is_finite() {
+        return Err(LTVError::EncodeError(format!("{} is not a finite number", value)));
+    }
+    let scaled = value * scale(decimals);
+    let units = scaled.round();
+    if (scaled - units).abs() > scaled.abs() * epsilon * 4.0 {
+        return Err(LTVError::EncodeError(format!(
+            "{} has more than {} decimal places",
+            value, decimals
+        )));
+    }
+    Ok(units)
+}
+
+macro_rules! impl_fixed_ltvitem {
+    ($($w:ident),+) => {
+    $(
+
+    impl_fixed_ltvitem!(@value $w, f32);
+    impl_fixed_ltvitem!(@value $w, f64);
+
+    )*
+    };
+    (@value $w:ident, $v:ident) => {
+
+    impl<const ED: ByteOrder, const DECIMALS: u32> LTVItem<ED> for Fixed<$w, DECIMALS, $v> {
+        const FIXED_SIZE: Option<usize> = <$w as LTVItem<ED>>::FIXED_SIZE;
+
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            let units = <$w as LTVItem<ED>>::from_ltv(field_id, data)?;
+            Ok(Self::new((units as f64 / scale(DECIMALS)) as $v))
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            <Self as LTVItem<ED>>::try_to_ltv(self)
+                .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+        }
+
+        fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+            let units = to_units(self.0 as f64, DECIMALS, $v::EPSILON as f64)?;
+            // `MAX as f64 + 1.0` is the exact power of two above `MAX`, even where `MAX as f64` rounds up
+            if units < $w::MIN as f64 || units >= $w::MAX as f64 + 1.0 {
+                return Err(LTVError::EncodeError(format!(
+                    "{} does not fit in {} with {} decimal places",
+                    self.0,
+                    stringify!($w),
+                    DECIMALS
+                )));
+            }
+            Ok(<$w as LTVItem<ED>>::to_ltv(&(units as $w)))
+        }
+    }
+
+    };
+}
+
+impl_fixed_ltvitem! {
+    u8,
+    i8,
+    u16,
+    i16,
+    u32,
+    i32,
+    u64,
+    i64
+}
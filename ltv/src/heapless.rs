@@ -0,0 +1,40 @@
+use crate::{
+    error::{LTVError, LTVResult},
+    ByteOrder, LTVItem, LTVItemMany,
+};
+
+impl<T: LTVItem<ED>, const N: usize, const ED: ByteOrder> LTVItemMany<ED> for ::heapless::Vec<T, N> {
+    type Item = T;
+    fn new() -> Self {
+        ::heapless::Vec::new()
+    }
+    /// More than `N` items fail with `LTVError::CapacityOverflow`.
+    fn add_item(&mut self, field_id: u8, v: Self::Item) -> LTVResult<()> {
+        self.push(v).map_err(|_| LTVError::CapacityOverflow {
+            field_id,
+            capacity: N,
+        })
+    }
+    fn get_items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn heapless_vec_overflow() {
+        let data = [2, 1, 0x0A, 2, 1, 0x0B, 2, 1, 0x0C];
+        let reader = LTVReaderBE::<1>::new(&data);
+
+        let items = reader.get_many::<u8, ::heapless::Vec<u8, 4>>(1).unwrap();
+        assert_eq!(&items[..], &[0x0A, 0x0B, 0x0C]);
+
+        assert!(matches!(
+            reader.get_many::<u8, ::heapless::Vec<u8, 2>>(1),
+            Err(LTVError::CapacityOverflow { field_id: 1, capacity: 2 })
+        ));
+    }
+}
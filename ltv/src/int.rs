@@ -0,0 +1,332 @@
+
+use crate::{
+    error::{LTVError, LTVResult},
+    object::copy_to_buffer,
+    ByteOrder, LTVItem,
+};
+
+/// An integer stored in `WIDTH` bytes on the wire instead of its full size,
+/// e.g. `NarrowInt<u32, 3>` for a 24 bit value.
+///
+/// Signed values are sign extended when decoding.
+/// Encoding a value that does not fit in `WIDTH` bytes fails with `LTVError::EncodeError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NarrowInt<T, const WIDTH: usize>(pub T);
+
+pub type U24 = NarrowInt<u32, 3>;
+pub type U40 = NarrowInt<u64, 5>;
+pub type U48 = NarrowInt<u64, 6>;
+pub type U56 = NarrowInt<u64, 7>;
+pub type I24 = NarrowInt<i32, 3>;
+pub type I40 = NarrowInt<i64, 5>;
+pub type I48 = NarrowInt<i64, 6>;
+pub type I56 = NarrowInt<i64, 7>;
+
+/// An integer written in the fewest bytes that hold its value,
+/// e.g. `Compact(5u32)` is a single byte.
+///
+/// Any length from 1 byte up to the size of the integer is accepted when decoding,
+/// signed values are sign extended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Compact<T>(pub T);
+
+/// LEB128 varint, signed values are zig-zag encoded first.
+///
+/// Decoding fails with `LTVError::InvalidVarInt` if the value is truncated,
+/// has trailing bytes, is not in its shortest form or does not fit the integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct VarInt<T>(pub T);
+
+pub type VarU16 = VarInt<u16>;
+pub type VarU32 = VarInt<u32>;
+pub type VarU64 = VarInt<u64>;
+pub type VarI16 = VarInt<i16>;
+pub type VarI32 = VarInt<i32>;
+pub type VarI64 = VarInt<i64>;
+
+/// Decodes a varint of at most `bits` bits that must span all of `data`.
+fn read_varint(field_id: u8, data: &[u8], bits: u32) -> LTVResult<u128> {
+    let invalid = |reason: &str| LTVError::InvalidVarInt(field_id, reason.into());
+
+    let mut value = 0u128;
+    for (i, b) in data.iter().enumerate() {
+        let shift = 7 * i as u32;
+        let low = (b & 0x7F) as u128;
+        if shift >= bits || (bits - shift < 7 && low >> (bits - shift) != 0) {
+            return Err(invalid("value too large"));
+        }
+        value |= low << shift;
+
+        if b & 0x80 == 0 {
+            if i + 1 != data.len() {
+                return Err(invalid("trailing bytes after varint"));
+            }
+            if i > 0 && low == 0 {
+                return Err(invalid("overlong varint"));
+            }
+            return Ok(value);
+        }
+    }
+    Err(invalid("truncated varint"))
+}
+
+fn write_varint(mut value: u128, buf: &mut [u8]) -> LTVResult<usize> {
+    let mut data = [0u8; 19];
+    let mut length = 0;
+    loop {
+        let b = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            data[length] = b;
+            length += 1;
+            break;
+        }
+        data[length] = b | 0x80;
+        length += 1;
+    }
+    copy_to_buffer(&data[..length], buf)
+}
+
+fn varint_to_vec(value: u128) -> Vec<u8> {
+    let mut data = vec![0; 19];
+    let length = write_varint(value, &mut data).expect("buffer sized for any varint");
+    data.truncate(length);
+    data
+}
+
+/// Integers that can be stored in fewer bytes than their size.
+trait PartialInt: Sized + Copy {
+    const SIZE: usize;
+
+    /// Reads 1 to `SIZE` bytes, sign extending signed values.
+    fn read_partial<const ED: ByteOrder>(data: &[u8]) -> Self;
+
+    /// Writes the least significant `width` bytes.
+    fn write_partial<const ED: ByteOrder>(self, width: usize, buf: &mut [u8]) -> LTVResult<usize>;
+
+    /// The fewest bytes that hold the value.
+    fn min_width(self) -> usize;
+}
+
+macro_rules! impl_int_adapters {
+    ($($i:ident),+) => {
+    $(
+
+    impl PartialInt for $i {
+        const SIZE: usize = std::mem::size_of::<$i>();
+
+        fn read_partial<const ED: ByteOrder>(data: &[u8]) -> Self {
+            let width = data.len();
+            let sign_byte = match ED {
+                ByteOrder::LE => data[width - 1],
+                ByteOrder::BE => data[0],
+            };
+            let fill = if $i::MIN != 0 && sign_byte & 0x80 != 0 { 0xFF } else { 0x00 };
+
+            let mut bytes = [fill; std::mem::size_of::<$i>()];
+            match ED {
+                ByteOrder::LE => {
+                    bytes[..width].copy_from_slice(data);
+                    $i::from_le_bytes(bytes)
+                }
+                ByteOrder::BE => {
+                    bytes[Self::SIZE - width..].copy_from_slice(data);
+                    $i::from_be_bytes(bytes)
+                }
+            }
+        }
+
+        fn write_partial<const ED: ByteOrder>(self, width: usize, buf: &mut [u8]) -> LTVResult<usize> {
+            match ED {
+                ByteOrder::LE => copy_to_buffer(&self.to_le_bytes()[..width], buf),
+                ByteOrder::BE => copy_to_buffer(&self.to_be_bytes()[Self::SIZE - width..], buf),
+            }
+        }
+
+        fn min_width(self) -> usize {
+            // signed values keep one extra bit for the sign
+            let bits = if $i::MIN != 0 {
+                // negative values have the top bit set
+                let magnitude = if self.leading_zeros() == 0 { !self } else { self };
+                $i::BITS - magnitude.leading_zeros() + 1
+            } else {
+                $i::BITS - self.leading_zeros()
+            };
+            (bits as usize).div_ceil(8).max(1)
+        }
+    }
+
+    impl<const WIDTH: usize> NarrowInt<$i, WIDTH> {
+        const VALID: () = assert!(WIDTH > 0 && WIDTH <= <$i as PartialInt>::SIZE, "WIDTH must be between 1 and the size of the integer");
+
+        pub const MIN: $i = if $i::MIN == 0 || WIDTH >= <$i as PartialInt>::SIZE {
+            $i::MIN
+        } else {
+            $i::MIN >> (8 * (<$i as PartialInt>::SIZE - WIDTH))
+        };
+        pub const MAX: $i = if WIDTH >= <$i as PartialInt>::SIZE {
+            $i::MAX
+        } else {
+            $i::MAX >> (8 * (<$i as PartialInt>::SIZE - WIDTH))
+        };
+
+        /// Returns `None` if `value` does not fit in `WIDTH` bytes.
+        pub fn new(value: $i) -> Option<Self> {
+            if (Self::MIN..=Self::MAX).contains(&value) {
+                Some(Self(value))
+            } else {
+                None
+            }
+        }
+
+        pub fn get(self) -> $i {
+            self.0
+        }
+    }
+
+    impl<const WIDTH: usize> From<$i> for NarrowInt<$i, WIDTH> {
+        fn from(value: $i) -> Self {
+            Self(value)
+        }
+    }
+
+    impl<const ED: ByteOrder, const WIDTH: usize> LTVItem<ED> for NarrowInt<$i, WIDTH> {
+        const FIXED_SIZE: Option<usize> = Some(WIDTH);
+
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            let () = Self::VALID;
+            if data.len() != WIDTH {
+                return Err(LTVError::WrongSize {
+                    field_id,
+                    expected: WIDTH,
+                    recieved: data.len(),
+                });
+            }
+            Ok(Self($i::read_partial::<ED>(data)))
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            <Self as LTVItem<ED>>::try_to_ltv(self)
+                .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+        }
+
+        fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+            let mut data = vec![0; WIDTH];
+            <Self as LTVItem<ED>>::encode_ltv(self, &mut data)?;
+            Ok(data)
+        }
+
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            let () = Self::VALID;
+            if self.0.min_width() > WIDTH {
+                return Err(LTVError::EncodeError(format!(
+                    "{} does not fit in {} bytes",
+                    self.0, WIDTH
+                )));
+            }
+            self.0.write_partial::<ED>(WIDTH, buf)
+        }
+    }
+
+    impl From<$i> for Compact<$i> {
+        fn from(value: $i) -> Self {
+            Self(value)
+        }
+    }
+
+    impl<const ED: ByteOrder> LTVItem<ED> for Compact<$i> {
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            if data.is_empty() || data.len() > <$i as PartialInt>::SIZE {
+                return Err(LTVError::WrongSize {
+                    field_id,
+                    expected: <$i as PartialInt>::SIZE,
+                    recieved: data.len(),
+                });
+            }
+            Ok(Self($i::read_partial::<ED>(data)))
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            let mut data = vec![0; self.0.min_width()];
+            <Self as LTVItem<ED>>::encode_ltv(self, &mut data).expect("buffer sized for value");
+            data
+        }
+
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            self.0.write_partial::<ED>(self.0.min_width(), buf)
+        }
+    }
+
+    )*
+    };
+}
+
+impl_int_adapters! {
+    u8,
+    i8,
+    u16,
+    i16,
+    u32,
+    i32,
+    u64,
+    i64,
+    u128,
+    i128
+}
+
+macro_rules! impl_varint {
+    ($($u:ident / $i:ident),+) => {
+    $(
+
+    impl From<$u> for VarInt<$u> {
+        fn from(value: $u) -> Self {
+            Self(value)
+        }
+    }
+
+    impl From<$i> for VarInt<$i> {
+        fn from(value: $i) -> Self {
+            Self(value)
+        }
+    }
+
+    impl<const ED: ByteOrder> LTVItem<ED> for VarInt<$u> {
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            Ok(Self(read_varint(field_id, data, $u::BITS)? as $u))
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            varint_to_vec(self.0 as u128)
+        }
+
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            write_varint(self.0 as u128, buf)
+        }
+    }
+
+    impl<const ED: ByteOrder> LTVItem<ED> for VarInt<$i> {
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            let zigzag = read_varint(field_id, data, $u::BITS)? as $u;
+            Ok(Self((zigzag >> 1) as $i ^ -((zigzag & 1) as $i)))
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            varint_to_vec(((self.0 << 1) ^ (self.0 >> ($i::BITS - 1))) as $u as u128)
+        }
+
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            write_varint(((self.0 << 1) ^ (self.0 >> ($i::BITS - 1))) as $u as u128, buf)
+        }
+    }
+
+    )*
+    };
+}
+
+impl_varint! {
+    u8 / i8,
+    u16 / i16,
+    u32 / i32,
+    u64 / i64,
+    u128 / i128
+}
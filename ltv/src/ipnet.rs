@@ -0,0 +1,86 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use ::ipnet::{IpNet, Ipv4Net, Ipv6Net};
+
+use crate::{
+    error::{LTVError, LTVResult},
+    ByteOrder, LTVItem,
+};
+
+fn invalid_prefix(field_id: u8, prefix_len: u8) -> LTVError {
+    LTVError::UnexpectedValue(field_id, format!("invalid prefix length {}", prefix_len))
+}
+
+/// The address followed by a 1 byte prefix length.
+impl<const ED: ByteOrder> LTVItem<ED> for Ipv4Net {
+    const FIXED_SIZE: Option<usize> = Some(5);
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        let (addr, prefix_len) = <(Ipv4Addr, u8) as LTVItem<ED>>::from_ltv(field_id, data)?;
+        Ipv4Net::new(addr, prefix_len).map_err(|_| invalid_prefix(field_id, prefix_len))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        <(Ipv4Addr, u8) as LTVItem<ED>>::to_ltv(&(self.addr(), self.prefix_len()))
+    }
+}
+
+/// The address followed by a 1 byte prefix length.
+impl<const ED: ByteOrder> LTVItem<ED> for Ipv6Net {
+    const FIXED_SIZE: Option<usize> = Some(17);
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        let (addr, prefix_len) = <(Ipv6Addr, u8) as LTVItem<ED>>::from_ltv(field_id, data)?;
+        Ipv6Net::new(addr, prefix_len).map_err(|_| invalid_prefix(field_id, prefix_len))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        <(Ipv6Addr, u8) as LTVItem<ED>>::to_ltv(&(self.addr(), self.prefix_len()))
+    }
+}
+
+/// `Ipv4Net` or `Ipv6Net` depending on the length of the value.
+impl<const ED: ByteOrder> LTVItem<ED> for IpNet {
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        match data.len() {
+            5 => Ok(IpNet::V4(<Ipv4Net as LTVItem<ED>>::from_ltv(field_id, data)?)),
+            17 => Ok(IpNet::V6(<Ipv6Net as LTVItem<ED>>::from_ltv(field_id, data)?)),
+            length => Err(LTVError::UnexpectedValue(
+                field_id,
+                format!("IP prefix must be 5 or 17 bytes, got {}", length),
+            )),
+        }
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        match self {
+            IpNet::V4(net) => <Ipv4Net as LTVItem<ED>>::to_ltv(net),
+            IpNet::V6(net) => <Ipv6Net as LTVItem<ED>>::to_ltv(net),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::ipnet::IpNet;
+
+    use crate::*;
+
+    #[test]
+    fn ip_prefixes() {
+        let net: IpNet = "10.1.0.0/16".parse().unwrap();
+        let data = <IpNet as LTVItem<{ ByteOrder::BE }>>::to_ltv(&net);
+        assert_eq!(data, vec![10, 1, 0, 0, 16]);
+        assert_eq!(<IpNet as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), net);
+
+        let net: IpNet = "fd00::/8".parse().unwrap();
+        let data = <IpNet as LTVItem<{ ByteOrder::BE }>>::to_ltv(&net);
+        assert_eq!(data.len(), 17);
+        assert_eq!(<IpNet as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), net);
+
+        assert!(matches!(
+            <IpNet as LTVItem<{ ByteOrder::BE }>>::from_ltv(4, &[10, 1, 0, 0, 33]),
+            Err(LTVError::UnexpectedValue(4, _))
+        ));
+    }
+}
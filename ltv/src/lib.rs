@@ -1,14 +1,58 @@
 #![feature(adt_const_params)]
 
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(feature = "bitflags")]
+mod bitflags;
+#[cfg(feature = "bytes")]
+mod bytes;
+#[cfg(feature = "heapless")]
+mod heapless;
 mod collection;
 mod error;
+mod fixed;
+mod int;
+#[cfg(feature = "ipnet")]
+mod ipnet;
+mod net;
 mod object;
 mod reader;
+#[cfg(feature = "semver")]
+mod semver;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+mod text;
+mod timestamp;
+mod tuple;
+#[cfg(feature = "uuid")]
+mod uuid;
+mod wrapper;
 mod writer;
 
 pub use ltv_derive::*;
 
-#[derive(Debug, PartialEq, Eq)]
+/// Used by the derive, which can not see the features `ltv` was built with.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "bytes")]
+    pub use ::bytes::Bytes;
+
+    /// Expands to the given items only with the `bytes` feature.
+    #[cfg(feature = "bytes")]
+    #[macro_export]
+    macro_rules! __with_bytes {
+        ($($item:tt)*) => { $($item)* };
+    }
+
+    /// Expands to the given items only with the `bytes` feature.
+    #[cfg(not(feature = "bytes"))]
+    #[macro_export]
+    macro_rules! __with_bytes {
+        ($($item:tt)*) => {};
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, std::marker::ConstParamTy)]
 pub enum ByteOrder {
     BE,
     LE,
@@ -17,18 +61,40 @@ pub enum ByteOrder {
 pub const DEFAULT_ED: ByteOrder = ByteOrder::BE;
 
 pub use error::{LTVError, LTVResult};
-pub use object::{LTVItem, LTVItemMany, LTVObject, LTVObjectConvertable, LTVObjectGroup};
+pub use fixed::Fixed;
+pub use int::{
+    Compact, NarrowInt, VarI16, VarI32, VarI64, VarInt, VarU16, VarU32, VarU64, I24, I40, I48, I56,
+    U24, U40, U48, U56,
+};
+pub use net::MacAddr;
+pub use object::{LTVItem, LTVItemMany, LTVItemMap, LTVObject, LTVObjectConvertable, LTVObjectGroup};
 pub use reader::LTVReader;
+pub use text::{Latin1, NulString, Utf16};
+pub use timestamp::{Time, TimeValue};
+pub use tuple::Positional;
 pub use writer::LTVContainer;
+pub use writer::LTVSliceWriter;
 pub use writer::LTVWriter;
+#[cfg(feature = "bytes")]
+pub use crate::bytes::{LTVBufMut, LTVBytesReader};
+#[cfg(feature = "bitflags")]
+pub use crate::bitflags::{BitFlags, Reject, Retain, Truncate, UnknownBits};
 
 //Helper types
 pub type LTVWriterBE<W, const LENGTH_SIZE: usize> = LTVWriter<W, { ByteOrder::BE }, LENGTH_SIZE>;
 pub type LTVWriterLE<W, const LENGTH_SIZE: usize> = LTVWriter<W, { ByteOrder::LE }, LENGTH_SIZE>;
 
+pub type LTVSliceWriterBE<'a, const LENGTH_SIZE: usize> = LTVSliceWriter<'a, { ByteOrder::BE }, LENGTH_SIZE>;
+pub type LTVSliceWriterLE<'a, const LENGTH_SIZE: usize> = LTVSliceWriter<'a, { ByteOrder::LE }, LENGTH_SIZE>;
+
 pub type LTVReaderBE<'a, const LENGTH_SIZE: usize> = LTVReader<'a, { ByteOrder::BE }, LENGTH_SIZE>;
 pub type LTVReaderLE<'a, const LENGTH_SIZE: usize> = LTVReader<'a, { ByteOrder::LE }, LENGTH_SIZE>;
 
+#[cfg(feature = "bytes")]
+pub type LTVBytesReaderBE<const LENGTH_SIZE: usize> = LTVBytesReader<{ ByteOrder::BE }, LENGTH_SIZE>;
+#[cfg(feature = "bytes")]
+pub type LTVBytesReaderLE<const LENGTH_SIZE: usize> = LTVBytesReader<{ ByteOrder::LE }, LENGTH_SIZE>;
+
 pub fn get_ltv<T: LTVItem<ED>, const ED: ByteOrder>(obj: &T) -> Vec<u8> {
     obj.to_ltv()
 }
@@ -97,6 +163,597 @@ mod tests {
         );
     }
 
+    #[test]
+    fn length_overflow() {
+        let data = vec![0u8; 255];
+        let mut writer = LTVWriterBE::<_, 1>::new(Vec::new());
+        match writer.write_ltv(0x01, &data) {
+            Err(LTVError::LengthOverflow { field_id: 1, length: 255, max: 254 }) => {}
+            e => panic!("expected LengthOverflow, got {:?}", e),
+        }
+
+        let mut writer = LTVWriterBE::<_, 2>::new(Vec::new());
+        writer.write_ltv(0x01, &data).unwrap();
+        let buffer = writer.into_inner();
+        assert_eq!(&buffer[..3], &[0x01, 0x00, 0x01]);
+        assert_eq!(LTVReaderBE::<2>::new(&buffer).get_item::<Vec<u8>>(0x01).unwrap(), data);
+    }
+
+    #[test]
+    fn slice_writer() {
+        let mut buf = [0u8; 8];
+        let mut writer = LTVSliceWriterBE::<1>::new(&mut buf);
+        writer.write_ltv(0x01, &0x35u8).unwrap();
+        writer.write_ltv(0x02, &0x1234u16).unwrap();
+        assert_eq!(writer.finish().unwrap(), 7);
+        assert_eq!(&buf[..7], &[2, 0x01, 0x35, 3, 0x02, 0x12, 0x34]);
+
+        let mut buf = [0u8; 4];
+        let mut writer = LTVSliceWriterBE::<1>::new(&mut buf);
+        writer.write_ltv(0x01, &0x35u8).unwrap();
+        writer.write_ltv(0x02, &0x1234u16).unwrap();
+        match writer.finish() {
+            Err(LTVError::BufferTooSmall { required: 7, available: 4 }) => {}
+            e => panic!("expected BufferTooSmall, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn canonical_writer() {
+        let mut writer = LTVWriterBE::<_, 1>::canonical(Vec::new());
+        writer.write_ltv(0x01, &None::<u8>).unwrap();
+        writer.write_ltv(0x02, &Some(Vec::<u8>::new())).unwrap();
+        writer.write_ltv(0x03, &0x35u8).unwrap();
+        assert!(matches!(
+            writer.write_ltv(0x02, &0x35u8),
+            Err(LTVError::UnorderedField { field_id: 2, previous: 3 })
+        ));
+
+        let buffer = writer.into_inner();
+        assert_eq!(&buffer, &[1, 0x02, 2, 0x03, 0x35]);
+        assert!(LTVReaderBE::<1>::is_canonical(&buffer));
+        assert_eq!(
+            LTVReaderBE::<1>::new(&buffer).get_item::<Option<Vec<u8>>>(0x02).unwrap(),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn is_canonical() {
+        assert!(LTVReaderBE::<1>::is_canonical(&[]));
+        assert!(LTVReaderBE::<1>::is_canonical(&[2, 0x01, 0x35, 2, 0x01, 0x36, 1, 0x02]));
+        // out of order
+        assert!(!LTVReaderBE::<1>::is_canonical(&[2, 0x02, 0x35, 2, 0x01, 0x36]));
+        // padding
+        assert!(!LTVReaderBE::<1>::is_canonical(&[2, 0x01, 0x35, 0]));
+        // truncated
+        assert!(!LTVReaderBE::<1>::is_canonical(&[3, 0x01, 0x35]));
+    }
+
+    /// Accepts at most two bytes per call, like a busy socket.
+    struct ShortWriter(Vec<u8>);
+    impl std::io::Write for ShortWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(2);
+            self.0.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn short_writes() {
+        let data: Vec<u8> = (0..100).collect();
+        let mut writer = LTVWriterLE::<_, 2>::new(ShortWriter(Vec::new()));
+        assert_eq!(writer.write_ltv(0x07, &data).unwrap(), 103);
+        assert_eq!(writer.write_ltv(0x08, &0x1234u16).unwrap(), 5);
+
+        let buffer = writer.into_inner().0;
+        let reader = LTVReaderLE::<2>::new(&buffer);
+        assert_eq!(reader.get_item::<Vec<u8>>(0x07).unwrap(), data);
+        assert_eq!(reader.get_item::<u16>(0x08).unwrap(), 0x1234);
+    }
+
+    #[test]
+    fn bool_char_float() {
+        let mut writer = LTVWriterLE::<_, 1>::new(Vec::new());
+        writer.write_ltv(0x01, &true).unwrap();
+        writer.write_ltv(0x02, &'ß').unwrap();
+        writer.write_ltv(0x03, &1.5f32).unwrap();
+        writer.write_ltv(0x04, &-2.25f64).unwrap();
+        let buffer = writer.into_inner();
+        assert_eq!(&buffer[..7], &[2, 0x01, 1, 5, 0x02, 0xDF, 0x00]);
+
+        let reader = LTVReaderLE::<1>::new(&buffer);
+        assert_eq!(reader.get_item::<bool>(0x01).unwrap(), true);
+        assert_eq!(reader.get_item::<char>(0x02).unwrap(), 'ß');
+        assert_eq!(reader.get_item::<f32>(0x03).unwrap(), 1.5);
+        assert_eq!(reader.get_item::<f64>(0x04).unwrap(), -2.25);
+        assert_eq!(
+            <f32 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&1.5),
+            1.5f32.to_be_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn invalid_bool_char() {
+        assert!(matches!(
+            <bool as LTVItem<{ ByteOrder::BE }>>::from_ltv(1, &[2]),
+            Err(LTVError::InvalidBool { field_id: 1, value: 2 })
+        ));
+        assert!(matches!(
+            <char as LTVItem<{ ByteOrder::BE }>>::from_ltv(2, &[0x00, 0x00, 0xD8, 0x00]),
+            Err(LTVError::InvalidChar { field_id: 2, value: 0xD800 })
+        ));
+        assert!(matches!(
+            <f64 as LTVItem<{ ByteOrder::BE }>>::from_ltv(3, &[0; 4]),
+            Err(LTVError::WrongSize { field_id: 3, expected: 8, recieved: 4 })
+        ));
+    }
+
+    #[test]
+    fn strings() {
+        let mut writer = LTVWriterLE::<_, 1>::new(Vec::new());
+        writer.write_ltv(0x01, &String::from("héllo")).unwrap();
+        writer.write_ltv(0x02, &NulString::from("abc")).unwrap();
+        writer.write_ltv(0x03, &Latin1::from("é")).unwrap();
+        writer.write_ltv(0x04, &Utf16::from("h€")).unwrap();
+        let buffer = writer.into_inner();
+        assert_eq!(
+            &buffer[8..],
+            &[5, 0x02, b'a', b'b', b'c', 0, 2, 0x03, 0xE9, 5, 0x04, b'h', 0, 0xAC, 0x20]
+        );
+
+        let reader = LTVReaderLE::<1>::new(&buffer);
+        assert_eq!(reader.get_item::<String>(0x01).unwrap(), "héllo");
+        assert_eq!(&*reader.get_item::<Box<str>>(0x01).unwrap(), "héllo");
+        assert_eq!(&*reader.get_item::<NulString>(0x02).unwrap(), "abc");
+        assert_eq!(&*reader.get_item::<Latin1>(0x03).unwrap(), "é");
+        assert_eq!(&*reader.get_item::<Utf16>(0x04).unwrap(), "h€");
+    }
+
+    #[test]
+    fn invalid_strings() {
+        assert!(matches!(
+            <String as LTVItem<{ ByteOrder::BE }>>::from_ltv(1, &[0xC3]),
+            Err(LTVError::InvalidUtf8 { field_id: 1, .. })
+        ));
+        assert!(matches!(
+            <NulString as LTVItem<{ ByteOrder::BE }>>::from_ltv(2, b"abc"),
+            Err(LTVError::InvalidText(2, _))
+        ));
+        assert!(matches!(
+            <Utf16 as LTVItem<{ ByteOrder::BE }>>::from_ltv(3, &[0xD8, 0x00]),
+            Err(LTVError::InvalidText(3, _))
+        ));
+        assert!(matches!(
+            <Latin1 as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&Latin1::from("€")),
+            Err(LTVError::EncodeError(_))
+        ));
+        assert!(matches!(
+            <NulString as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&NulString::from("a\0b")),
+            Err(LTVError::EncodeError(_))
+        ));
+    }
+
+    #[test]
+    fn packed_numbers() {
+        let mut writer = LTVWriterLE::<_, 1>::new(Vec::new());
+        writer.write_ltv(0x01, &[0x0102u16, 0x0304]).unwrap();
+        writer.write_ltv(0x02, &vec![-1i32, 2]).unwrap();
+        writer.write_ltv(0x03, &vec![0.5f32]).unwrap();
+        let buffer = writer.into_inner();
+        assert_eq!(&buffer[..6], &[5, 0x01, 0x02, 0x01, 0x04, 0x03]);
+
+        let reader = LTVReaderLE::<1>::new(&buffer);
+        assert_eq!(reader.get_item::<[u16; 2]>(0x01).unwrap(), [0x0102, 0x0304]);
+        assert_eq!(reader.get_item::<Vec<u16>>(0x01).unwrap(), vec![0x0102, 0x0304]);
+        assert_eq!(reader.get_item::<Vec<i32>>(0x02).unwrap(), vec![-1, 2]);
+        assert_eq!(reader.get_item::<Vec<f32>>(0x03).unwrap(), vec![0.5]);
+
+        assert!(matches!(
+            reader.get_item::<[u16; 3]>(0x01),
+            Err(LTVError::WrongSize { field_id: 1, expected: 6, recieved: 4 })
+        ));
+        assert!(matches!(
+            <Vec<u32> as LTVItem<{ ByteOrder::BE }>>::from_ltv(4, &[0; 6]),
+            Err(LTVError::WrongSize { field_id: 4, expected: 8, recieved: 6 })
+        ));
+
+        let mut buf = [0u8; 4];
+        assert_eq!(<[u16; 2] as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&[0x0102, 0x0304], &mut buf).unwrap(), 4);
+        assert_eq!(buf, [0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn narrow_ints() {
+        assert_eq!(<U24 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&U24::new(0x010203).unwrap()), vec![0x01, 0x02, 0x03]);
+        assert_eq!(<U24 as LTVItem<{ ByteOrder::LE }>>::to_ltv(&U24::new(0x010203).unwrap()), vec![0x03, 0x02, 0x01]);
+        assert_eq!(<U48 as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0, 0, 0, 0, 0x01, 0x02]).unwrap().get(), 0x0102);
+
+        assert_eq!(<I24 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&I24::new(-2).unwrap()), vec![0xFF, 0xFF, 0xFE]);
+        assert_eq!(<I24 as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0xFF, 0xFF, 0xFE]).unwrap().get(), -2);
+        assert_eq!(<I40 as LTVItem<{ ByteOrder::LE }>>::from_ltv(0, &[0, 0, 0, 0, 0x80]).unwrap().get(), I40::MIN);
+
+        assert_eq!(U24::MAX, 0xFF_FFFF);
+        assert_eq!(I24::MIN, -0x80_0000);
+        assert!(U24::new(0x0100_0000).is_none());
+        assert!(matches!(
+            <U24 as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&NarrowInt(0x0100_0000)),
+            Err(LTVError::EncodeError(_))
+        ));
+        assert!(matches!(
+            <U24 as LTVItem<{ ByteOrder::BE }>>::from_ltv(1, &[0, 0, 0, 0]),
+            Err(LTVError::WrongSize { field_id: 1, expected: 3, recieved: 4 })
+        ));
+    }
+
+    #[test]
+    fn compact_ints() {
+        assert_eq!(<Compact<u32> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Compact(5)), vec![5]);
+        assert_eq!(<Compact<u32> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Compact(0)), vec![0]);
+        assert_eq!(<Compact<u32> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Compact(0x0102)), vec![0x01, 0x02]);
+        assert_eq!(<Compact<u32> as LTVItem<{ ByteOrder::LE }>>::to_ltv(&Compact(0x0102)), vec![0x02, 0x01]);
+        assert_eq!(<Compact<u8> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Compact(0xFF)), vec![0xFF]);
+        assert_eq!(<Compact<i16> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Compact(-1)), vec![0xFF]);
+        assert_eq!(<Compact<i16> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Compact(128)), vec![0x00, 0x80]);
+        assert_eq!(<Compact<i32> as LTVItem<{ ByteOrder::LE }>>::to_ltv(&Compact(-129)), vec![0x7F, 0xFF]);
+
+        assert_eq!(<Compact<u32> as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[5]).unwrap().0, 5);
+        assert_eq!(<Compact<u32> as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0, 0, 1, 2]).unwrap().0, 0x0102);
+        assert_eq!(<Compact<i32> as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0xFF, 0x7F]).unwrap().0, -129);
+        assert_eq!(<Compact<i32> as LTVItem<{ ByteOrder::LE }>>::from_ltv(0, &[0x7F, 0xFF]).unwrap().0, -129);
+        assert!(matches!(
+            <Compact<u16> as LTVItem<{ ByteOrder::BE }>>::from_ltv(1, &[0, 0, 1]),
+            Err(LTVError::WrongSize { field_id: 1, expected: 2, recieved: 3 })
+        ));
+        assert!(<Compact<u16> as LTVItem<{ ByteOrder::BE }>>::from_ltv(1, &[]).is_err());
+    }
+
+    #[test]
+    fn varints() {
+        assert_eq!(<VarU64 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&VarInt(0)), vec![0x00]);
+        assert_eq!(<VarU64 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&VarInt(300)), vec![0xAC, 0x02]);
+        assert_eq!(<VarU64 as LTVItem<{ ByteOrder::LE }>>::to_ltv(&VarInt(u64::MAX)).len(), 10);
+        assert_eq!(<VarI64 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&VarInt(-1)), vec![0x01]);
+        assert_eq!(<VarI64 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&VarInt(1)), vec![0x02]);
+        assert_eq!(<VarI64 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&VarInt(-65)), vec![0x81, 0x01]);
+
+        for v in [0, 1, -1, 63, -64, i64::MAX, i64::MIN] {
+            let data = <VarI64 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&VarInt(v));
+            assert_eq!(<VarI64 as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap().0, v);
+        }
+        assert_eq!(<VarU64 as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0xAC, 0x02]).unwrap().0, 300);
+        let max = <VarU64 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&VarInt(u64::MAX));
+        assert_eq!(<VarU64 as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &max).unwrap().0, u64::MAX);
+
+        let invalid = |data: &[u8]| {
+            matches!(
+                <VarU16 as LTVItem<{ ByteOrder::BE }>>::from_ltv(1, data),
+                Err(LTVError::InvalidVarInt(1, _))
+            )
+        };
+        assert!(invalid(&[]));
+        assert!(invalid(&[0x80]));
+        assert!(invalid(&[0x01, 0x02]));
+        assert!(invalid(&[0x81, 0x00]));
+        assert!(invalid(&[0xFF, 0xFF, 0x04]));
+        assert!(invalid(&[0x80, 0x80, 0x80, 0x01]));
+        assert!(!invalid(&[0xFF, 0xFF, 0x03]));
+    }
+
+    #[test]
+    fn tuples() {
+        type Range = (u16, u16);
+        assert_eq!(<Range as LTVItem<{ ByteOrder::BE }>>::FIXED_SIZE, Some(4));
+        assert_eq!(<Range as LTVItem<{ ByteOrder::BE }>>::to_ltv(&(1, 2)), vec![0, 1, 0, 2]);
+        assert_eq!(<Range as LTVItem<{ ByteOrder::LE }>>::from_ltv(0, &[1, 0, 2, 0]).unwrap(), (1, 2));
+        assert!(matches!(
+            <Range as LTVItem<{ ByteOrder::BE }>>::from_ltv(1, &[0, 1, 0]),
+            Err(LTVError::WrongSize { field_id: 1, expected: 4, recieved: 3 })
+        ));
+
+        type MacEntry = (u8, [u8; 6]);
+        let entry: MacEntry = (3, [1, 2, 3, 4, 5, 6]);
+        let data = <MacEntry as LTVItem<{ ByteOrder::BE }>>::to_ltv(&entry);
+        assert_eq!(data, vec![3, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(<MacEntry as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), entry);
+
+        // the last member can have a variable size
+        type Tagged = (u8, String);
+        assert_eq!(
+            <Tagged as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[7, b'h', b'i']).unwrap(),
+            (7, String::from("hi"))
+        );
+        assert!(<Tagged as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[]).is_err());
+
+        // encoded in place like `to_ltv`, reporting the full size when it does not fit
+        let mut buf = [0u8; 8];
+        let length = <MacEntry as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&entry, &mut buf).unwrap();
+        assert_eq!(&buf[..length], &[3, 1, 2, 3, 4, 5, 6]);
+        let tagged: Tagged = (7, String::from("hi"));
+        let length = <Tagged as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&tagged, &mut buf).unwrap();
+        assert_eq!(&buf[..length], &[7, b'h', b'i']);
+        assert!(matches!(
+            <MacEntry as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&entry, &mut buf[..3]),
+            Err(LTVError::BufferTooSmall { required: 7, available: 3 })
+        ));
+    }
+
+    #[test]
+    fn positional_tuples() {
+        type Entry = Positional<(u8, String, Option<u16>)>;
+        let entry: Entry = Positional((1, String::from("a"), None));
+        let data = <Entry as LTVItem<{ ByteOrder::BE }>>::to_ltv(&entry);
+        assert_eq!(data, vec![2, 1, 1, 2, 2, b'a']);
+        assert_eq!(<Entry as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), entry);
+
+        let entry: Entry = Positional((1, String::from("a"), Some(5)));
+        let data = <Entry as LTVItem<{ ByteOrder::BE }>>::to_ltv(&entry);
+        assert_eq!(<Entry as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), entry);
+
+        let mut buf = [0u8; 16];
+        let length = <Entry as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&entry, &mut buf).unwrap();
+        assert_eq!(&buf[..length], &data[..]);
+        assert!(matches!(
+            <Entry as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&entry, &mut buf[..4]),
+            Err(LTVError::BufferTooSmall { required: 10, available: 4 })
+        ));
+    }
+
+    #[test]
+    fn maps() {
+        use std::collections::{BTreeMap, HashMap};
+
+        let mut map = BTreeMap::new();
+        map.insert(1u8, String::from("a"));
+        map.insert(2u8, String::from("bc"));
+
+        let mut writer = LTVWriterBE::<_, 1>::new(Vec::new());
+        writer.write_ltv_map(5, 1, 2, &map).unwrap();
+        let data = writer.into_inner();
+        assert_eq!(
+            data,
+            vec![7, 5, 2, 1, 1, 2, 2, b'a', 8, 5, 2, 1, 2, 3, 2, b'b', b'c']
+        );
+
+        let reader = LTVReaderBE::<1>::new(&data);
+        assert_eq!(reader.get_map::<BTreeMap<u8, String>>(5, 1, 2).unwrap(), map);
+        let hash_map = reader.get_map::<HashMap<u8, String>>(5, 1, 2).unwrap();
+        assert_eq!(hash_map.len(), 2);
+        assert_eq!(hash_map[&2], "bc");
+
+        let duplicate = [data.clone(), data[..8].to_vec()].concat();
+        assert!(matches!(
+            LTVReaderBE::<1>::new(&duplicate).get_map::<HashMap<u8, String>>(5, 1, 2),
+            Err(LTVError::DuplicateKey(5))
+        ));
+    }
+
+    #[test]
+    fn maps_with_empty_entries() {
+        use std::collections::BTreeMap;
+
+        let mut empty_value = BTreeMap::new();
+        empty_value.insert(3u16, String::new());
+        let mut writer = LTVWriterBE::<_, 1>::new(Vec::new());
+        writer.write_ltv_map(5, 1, 2, &empty_value).unwrap();
+        let data = writer.into_inner();
+        assert_eq!(data, vec![7, 5, 3, 1, 0, 3, 1, 2]);
+        let reader = LTVReaderBE::<1>::new(&data);
+        assert_eq!(reader.get_map::<BTreeMap<u16, String>>(5, 1, 2).unwrap(), empty_value);
+
+        let mut empty_key = BTreeMap::new();
+        empty_key.insert(String::new(), 7u8);
+        let mut writer = LTVWriterBE::<_, 1>::new(Vec::new());
+        writer.write_ltv_map(5, 1, 2, &empty_key).unwrap();
+        let data = writer.into_inner();
+        assert_eq!(data, vec![6, 5, 1, 1, 2, 2, 7]);
+        let reader = LTVReaderBE::<1>::new(&data);
+        assert_eq!(reader.get_map::<BTreeMap<String, u8>>(5, 1, 2).unwrap(), empty_key);
+    }
+
+    #[test]
+    fn collections() {
+        use std::collections::{BTreeSet, HashSet, VecDeque};
+
+        let data = [2, 1, 0x0B, 2, 1, 0x0A, 2, 2, 0xFF];
+        let reader = LTVReaderBE::<1>::new(&data);
+
+        let deque = reader.get_many::<u8, VecDeque<u8>>(1).unwrap();
+        assert_eq!(deque, VecDeque::from(vec![0x0B, 0x0A]));
+        let boxed = reader.get_many::<u8, Box<[u8]>>(1).unwrap();
+        assert_eq!(&boxed[..], &[0x0B, 0x0A]);
+        let set = reader.get_many::<u8, BTreeSet<u8>>(1).unwrap();
+        assert_eq!(
+            <BTreeSet<u8> as LTVItemMany<{ ByteOrder::BE }>>::get_items(&set).collect::<Vec<_>>(),
+            vec![&0x0A, &0x0B]
+        );
+
+        let duplicate = [2, 1, 0x0A, 2, 1, 0x0A];
+        assert!(matches!(
+            LTVReaderBE::<1>::new(&duplicate).get_many::<u8, HashSet<u8>>(1),
+            Err(LTVError::DuplicateKey(1))
+        ));
+
+        // canonical writers sort unordered lists by the encoded item
+        let set = (0..32u8).rev().collect::<HashSet<u8>>();
+        let mut writer = LTVWriterBE::<_, 1>::canonical(Vec::new());
+        writer.write_ltv_list(1, &set).unwrap();
+        let data = writer.into_inner();
+        assert_eq!(data.chunks(3).map(|item| item[2]).collect::<Vec<_>>(), (0..32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn wrappers() {
+        use std::borrow::Cow;
+        use std::num::Wrapping;
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        assert_eq!(<Box<u16> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Box::new(0x0102)), vec![1, 2]);
+        assert_eq!(*<Rc<u16> as LTVItem<{ ByteOrder::LE }>>::from_ltv(0, &[1, 2]).unwrap(), 0x0201);
+        assert_eq!(<Arc<Vec<u8>> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Arc::new(vec![7])), vec![7]);
+        assert_eq!(<Wrapping<u8> as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[9]).unwrap(), Wrapping(9));
+
+        let reader = LTVReaderBE::<1>::new(&[2, 1, 5]);
+        assert_eq!(reader.get_item::<Box<Option<u8>>>(1).unwrap(), Box::new(Some(5)));
+        assert_eq!(reader.get_item::<Box<Option<u8>>>(2).unwrap(), Box::new(None));
+        assert_eq!(reader.get_item::<Wrapping<Option<u8>>>(2).unwrap(), Wrapping(None));
+        assert!(matches!(reader.get_item::<Wrapping<u8>>(2), Err(LTVError::NotFound(2))));
+
+        // absent wrapped values are left out of canonical output like the value itself
+        let mut writer = LTVWriterBE::<_, 1>::canonical(Vec::new());
+        writer.write_ltv(1, &Wrapping(None::<u8>)).unwrap();
+        writer.write_ltv(2, &Wrapping(Some(Vec::<u8>::new()))).unwrap();
+        assert_eq!(writer.into_inner(), vec![1, 2]);
+
+        let bytes: Cow<[u8]> = Cow::Borrowed(&[1, 2]);
+        assert_eq!(<Cow<[u8]> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&bytes), vec![1, 2]);
+        let text = <Cow<str> as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, b"hi").unwrap();
+        assert_eq!(text, "hi");
+        assert!(<Cow<str> as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0xFF]).is_err());
+    }
+
+    #[test]
+    fn net_addresses() {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+        let ip = Ipv4Addr::new(192, 168, 0, 1);
+        assert_eq!(<Ipv4Addr as LTVItem<{ ByteOrder::LE }>>::to_ltv(&ip), vec![192, 168, 0, 1]);
+        assert_eq!(<IpAddr as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[192, 168, 0, 1]).unwrap(), IpAddr::V4(ip));
+        let ip6 = <IpAddr as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &Ipv6Addr::LOCALHOST.octets()).unwrap();
+        assert_eq!(ip6, IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert!(matches!(
+            <IpAddr as LTVItem<{ ByteOrder::BE }>>::from_ltv(3, &[1, 2, 3]),
+            Err(LTVError::UnexpectedValue(3, _))
+        ));
+        assert!(matches!(
+            <Ipv4Addr as LTVItem<{ ByteOrder::BE }>>::from_ltv(3, &[1, 2, 3]),
+            Err(LTVError::WrongSize { field_id: 3, expected: 4, recieved: 3 })
+        ));
+
+        let socket = SocketAddrV4::new(ip, 8080);
+        let data = <SocketAddrV4 as LTVItem<{ ByteOrder::BE }>>::to_ltv(&socket);
+        assert_eq!(data, vec![192, 168, 0, 1, 0x1F, 0x90]);
+        assert_eq!(<SocketAddr as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), SocketAddr::V4(socket));
+
+        // encoded in place like `to_ltv`
+        let mut buf = [0u8; 18];
+        let length = <SocketAddr as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&SocketAddr::V4(socket), &mut buf).unwrap();
+        assert_eq!(&buf[..length], &data[..]);
+        let socket6 = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0);
+        let length = <SocketAddrV6 as LTVItem<{ ByteOrder::LE }>>::encode_ltv(&socket6, &mut buf).unwrap();
+        assert_eq!(&buf[..length], &<SocketAddrV6 as LTVItem<{ ByteOrder::LE }>>::to_ltv(&socket6)[..]);
+        assert_eq!(&buf[16..], &[0x90, 0x1F]);
+        assert!(matches!(
+            <SocketAddrV4 as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&socket, &mut buf[..4]),
+            Err(LTVError::BufferTooSmall { required: 6, available: 4 })
+        ));
+
+        let mac = MacAddr([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+        assert_eq!(mac.to_string(), "00:1a:2b:3c:4d:5e");
+        assert_eq!(<MacAddr as LTVItem<{ ByteOrder::BE }>>::to_ltv(&mac), mac.octets().to_vec());
+        assert_eq!(<MacAddr as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &mac.octets()).unwrap(), mac);
+    }
+
+    #[test]
+    fn times() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        let duration = Duration::from_millis(1500);
+        assert_eq!(<Duration as LTVItem<{ ByteOrder::BE }>>::to_ltv(&duration), vec![0, 0, 0, 0, 0, 0, 0x05, 0xDC]);
+        assert_eq!(<Time<Duration, 1, 4> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Time(duration)), vec![0, 0, 0, 1]);
+
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let data = <SystemTime as LTVItem<{ ByteOrder::LE }>>::to_ltv(&now);
+        assert_eq!(<SystemTime as LTVItem<{ ByteOrder::LE }>>::from_ltv(0, &data).unwrap(), now);
+
+        // 32 bit seconds since 2000-01-01
+        type Y2K = Time<SystemTime, 1, 4, 946_684_800>;
+        let data = <Y2K as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Time(UNIX_EPOCH + Duration::from_secs(946_684_801)));
+        assert_eq!(data, vec![0, 0, 0, 1]);
+        assert_eq!(
+            <Y2K as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap().0,
+            UNIX_EPOCH + Duration::from_secs(946_684_801)
+        );
+        assert!(matches!(
+            <Y2K as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&Time(UNIX_EPOCH)),
+            Err(LTVError::EncodeError(_))
+        ));
+        assert!(matches!(
+            <Time<Duration, 1_000_000, 4> as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&Time(Duration::from_secs(5000))),
+            Err(LTVError::EncodeError(_))
+        ));
+    }
+
+    #[test]
+    fn plain_times_truncate_to_millis() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        let duration = Duration::new(2, 345_678_901);
+        let data = <Duration as LTVItem<{ ByteOrder::BE }>>::to_ltv(&duration);
+        assert_eq!(
+            <Duration as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(),
+            Duration::from_millis(2345)
+        );
+
+        let time = UNIX_EPOCH + Duration::new(1_700_000_000, 999_999);
+        let data = <SystemTime as LTVItem<{ ByteOrder::BE }>>::to_ltv(&time);
+        assert_eq!(
+            <SystemTime as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(),
+            UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+        );
+
+        // nanoseconds keep everything
+        type Nanos = Time<Duration, 1_000_000_000>;
+        let data = <Nanos as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Time(duration));
+        assert_eq!(<Nanos as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap().0, duration);
+    }
+
+    #[test]
+    fn fixed_point() {
+        type Celsius = Fixed<i16, 2>;
+        assert_eq!(<Celsius as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Fixed::new(21.37)), vec![0x08, 0x59]);
+        assert_eq!(<Celsius as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0xF7, 0xA7]).unwrap().get(), -21.37);
+        assert_eq!(<Fixed<u8, 1, f32> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Fixed::new(2.5)), vec![25]);
+
+        assert!(matches!(
+            <Celsius as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&Fixed::new(21.375)),
+            Err(LTVError::EncodeError(_))
+        ));
+        assert!(matches!(
+            <Celsius as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&Fixed::new(327.68)),
+            Err(LTVError::EncodeError(_))
+        ));
+        assert!(matches!(
+            <Celsius as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&Fixed::new(f64::NAN)),
+            Err(LTVError::EncodeError(_))
+        ));
+        assert_eq!(<Fixed<u64, 0> as LTVItem<{ ByteOrder::BE }>>::FIXED_SIZE, Some(8));
+        assert!(<Fixed<u64, 0> as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&Fixed::new(u64::MAX as f64)).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_times() {
+        let time = ::chrono::DateTime::<::chrono::Utc>::from_timestamp(1_700_000_000, 5_000_000).unwrap();
+        let data = <::chrono::DateTime<::chrono::Utc> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&time);
+        assert_eq!(<u64 as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), 1_700_000_000_005);
+        assert_eq!(<::chrono::DateTime<::chrono::Utc> as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), time);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_crate_times() {
+        let time = ::time::OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let data = <Time<::time::OffsetDateTime, 1, 4> as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Time(time));
+        assert_eq!(<u32 as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), 1_700_000_000);
+        assert_eq!(<::time::OffsetDateTime as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0, 0, 0, 0, 0, 0, 0x03, 0xE8]).unwrap(), ::time::OffsetDateTime::from_unix_timestamp(1).unwrap());
+    }
+
     #[test]
     fn zeros() {
         let data = vec![
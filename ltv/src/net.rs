@@ -0,0 +1,161 @@
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use crate::{
+    error::{LTVError, LTVResult},
+    object::copy_to_buffer,
+    ByteOrder, LTVItem,
+};
+
+/// A 6 byte ethernet MAC address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl MacAddr {
+    pub fn octets(&self) -> [u8; 6] {
+        self.0
+    }
+}
+
+impl From<[u8; 6]> for MacAddr {
+    fn from(octets: [u8; 6]) -> Self {
+        Self(octets)
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}", a, b, c, d, e, g)
+    }
+}
+
+/// Addresses are always written in network order (as their octets), whatever the field byte order.
+macro_rules! impl_address_ltvitem {
+    ($($t:ident: $size:literal),+) => {
+    $(
+
+    impl<const ED: ByteOrder> LTVItem<ED> for $t {
+        const FIXED_SIZE: Option<usize> = Some($size);
+
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            Ok($t::from(<[u8; $size] as LTVItem<ED>>::from_ltv(field_id, data)?))
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            self.octets().to_vec()
+        }
+
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            copy_to_buffer(&self.octets(), buf)
+        }
+    }
+
+    )*
+    };
+}
+
+impl_address_ltvitem! {
+    Ipv4Addr: 4,
+    Ipv6Addr: 16,
+    MacAddr: 6
+}
+
+/// IPv4 or IPv6 depending on the length of the value.
+impl<const ED: ByteOrder> LTVItem<ED> for IpAddr {
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        match data.len() {
+            4 => Ok(IpAddr::V4(<Ipv4Addr as LTVItem<ED>>::from_ltv(field_id, data)?)),
+            16 => Ok(IpAddr::V6(<Ipv6Addr as LTVItem<ED>>::from_ltv(field_id, data)?)),
+            length => Err(LTVError::UnexpectedValue(
+                field_id,
+                format!("IP address must be 4 or 16 bytes, got {}", length),
+            )),
+        }
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        match self {
+            IpAddr::V4(addr) => <Ipv4Addr as LTVItem<ED>>::to_ltv(addr),
+            IpAddr::V6(addr) => <Ipv6Addr as LTVItem<ED>>::to_ltv(addr),
+        }
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        match self {
+            IpAddr::V4(addr) => <Ipv4Addr as LTVItem<ED>>::encode_ltv(addr, buf),
+            IpAddr::V6(addr) => <Ipv6Addr as LTVItem<ED>>::encode_ltv(addr, buf),
+        }
+    }
+}
+
+/// The address followed by the port, in the field byte order.
+impl<const ED: ByteOrder> LTVItem<ED> for SocketAddrV4 {
+    const FIXED_SIZE: Option<usize> = Some(6);
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        let (ip, port) = <(Ipv4Addr, u16) as LTVItem<ED>>::from_ltv(field_id, data)?;
+        Ok(SocketAddrV4::new(ip, port))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        <(Ipv4Addr, u16) as LTVItem<ED>>::to_ltv(&(*self.ip(), self.port()))
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        let mut data = [0u8; 6];
+        data[..4].copy_from_slice(&self.ip().octets());
+        <u16 as LTVItem<ED>>::encode_ltv(&self.port(), &mut data[4..])?;
+        copy_to_buffer(&data, buf)
+    }
+}
+
+/// The address followed by the port, in the field byte order.
+/// Flow info and scope id are not written and decode as 0.
+impl<const ED: ByteOrder> LTVItem<ED> for SocketAddrV6 {
+    const FIXED_SIZE: Option<usize> = Some(18);
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        let (ip, port) = <(Ipv6Addr, u16) as LTVItem<ED>>::from_ltv(field_id, data)?;
+        Ok(SocketAddrV6::new(ip, port, 0, 0))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        <(Ipv6Addr, u16) as LTVItem<ED>>::to_ltv(&(*self.ip(), self.port()))
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        let mut data = [0u8; 18];
+        data[..16].copy_from_slice(&self.ip().octets());
+        <u16 as LTVItem<ED>>::encode_ltv(&self.port(), &mut data[16..])?;
+        copy_to_buffer(&data, buf)
+    }
+}
+
+/// `SocketAddrV4` or `SocketAddrV6` depending on the length of the value.
+impl<const ED: ByteOrder> LTVItem<ED> for SocketAddr {
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        match data.len() {
+            6 => Ok(SocketAddr::V4(<SocketAddrV4 as LTVItem<ED>>::from_ltv(field_id, data)?)),
+            18 => Ok(SocketAddr::V6(<SocketAddrV6 as LTVItem<ED>>::from_ltv(field_id, data)?)),
+            length => Err(LTVError::UnexpectedValue(
+                field_id,
+                format!("socket address must be 6 or 18 bytes, got {}", length),
+            )),
+        }
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        match self {
+            SocketAddr::V4(addr) => <SocketAddrV4 as LTVItem<ED>>::to_ltv(addr),
+            SocketAddr::V6(addr) => <SocketAddrV6 as LTVItem<ED>>::to_ltv(addr),
+        }
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        match self {
+            SocketAddr::V4(addr) => <SocketAddrV4 as LTVItem<ED>>::encode_ltv(addr, buf),
+            SocketAddr::V6(addr) => <SocketAddrV6 as LTVItem<ED>>::encode_ltv(addr, buf),
+        }
+    }
+}
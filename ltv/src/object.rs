@@ -1,41 +1,242 @@
 use std::{convert::{TryInto}, u128};
+use std::collections::{btree_map, hash_map, BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::hash::{BuildHasher, Hash};
 
 use crate::{
     error::{LTVError, LTVResult},
+    writer::{encode_header, encode_value},
     ByteOrder,
 };
 
+/// Copies an already encoded value into `buf`, used by `LTVItem::encode_ltv`.
+pub(crate) fn copy_to_buffer(data: &[u8], buf: &mut [u8]) -> LTVResult<usize> {
+    match buf.get_mut(..data.len()) {
+        Some(out) => {
+            out.copy_from_slice(data);
+            Ok(data.len())
+        }
+        None => Err(LTVError::BufferTooSmall {
+            required: data.len(),
+            available: buf.len(),
+        }),
+    }
+}
+
 pub trait LTVObjectGroup<'a, const ED: ByteOrder>: Sized {
     fn to_ltv(&self) -> Vec<u8>;
     fn from_ltv(data: &'a [u8]) -> Option<Self>;
 }
 
 pub trait LTVItem<const ED: ByteOrder>: Sized {
+    /// The encoded size if every value has the same size (e.g. `u32`),
+    /// used to split concatenated values such as tuples.
+    const FIXED_SIZE: Option<usize> = None;
+
     fn from_ltv(field_type: u8, data: &[u8]) -> LTVResult<Self>;
     fn to_ltv(&self) -> Vec<u8>;
 
+    /// Decode from a slice of a `Bytes` buffer, used by `LTVBytesReader`.
+    /// Items that can keep a reference to the buffer (e.g. `Bytes`) override this to avoid copying.
+    #[cfg(feature = "bytes")]
+    fn from_ltv_bytes(field_type: u8, data: ::bytes::Bytes) -> LTVResult<Self> {
+        Self::from_ltv(field_type, &data)
+    }
+
+    /// Fallible version of `to_ltv`.
+    /// Items that can fail to encode (e.g. a field that does not fit in its length header)
+    /// should override this and have `to_ltv` call it.
+    fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+        Ok(self.to_ltv())
+    }
+
+    /// Writes the value into `buf` without allocating and returns the number of bytes written.
+    /// If `buf` is too small, returns `LTVError::BufferTooSmall` with the size the value needs.
+    ///
+    /// The default implementation encodes with `try_to_ltv` and copies the result.
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(&self.try_to_ltv()?, buf)
+    }
+
+    /// The encoded value, for items that already hold it as a slice (e.g. `Vec<u8>`).
+    /// Writers use it to send the value without copying it.
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        None
+    }
+
     fn not_found(field_id: u8) -> LTVResult<Self> {
         Err(LTVError::NotFound(field_id))
     }
+
+    /// `false` for values that should not be written at all, such as `None`.
+    /// Canonical writers use this to tell an absent field from a present but empty one.
+    fn is_present(&self) -> bool {
+        true
+    }
 }
 
 
 pub trait LTVItemMany<const ED: ByteOrder>: Sized {
     type Item: LTVItem<ED>;
+    /// `true` if the iteration order is not deterministic, canonical writers sort these items.
+    const UNORDERED: bool = false;
     fn new() -> Self;
-    fn add_item(&mut self, v: Self::Item);
-    fn get_items<'a>(&'a self) -> Vec<&'a Self::Item>;
+    /// Adds an item read from field `field_id`.
+    /// Fails if the collection can not take it (e.g. it is full or already has the item).
+    fn add_item(&mut self, field_id: u8, v: Self::Item) -> LTVResult<()>;
+    fn get_items(&self) -> impl Iterator<Item = &Self::Item>;
+
+    /// Builds the collection from the items read from field `field_id`, stopping at the first error.
+    fn from_items(field_id: u8, items: impl Iterator<Item = LTVResult<Self::Item>>) -> LTVResult<Self> {
+        let mut many = Self::new();
+        for item in items {
+            many.add_item(field_id, item?)?;
+        }
+        Ok(many)
+    }
 }
 impl<T: LTVItem<ED>, const ED: ByteOrder> LTVItemMany<ED> for Vec<T> {
     type Item = T;
     fn new() -> Self {
         Vec::new()
     }
-    fn add_item(&mut self, v: Self::Item) {
-        self.push(v)
+    fn add_item(&mut self, _field_id: u8, v: Self::Item) -> LTVResult<()> {
+        self.push(v);
+        Ok(())
     }
-    fn get_items<'a>(&'a self) -> Vec<&'a Self::Item> {
-        self.iter().collect()
+    fn get_items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+impl<T: LTVItem<ED>, const ED: ByteOrder> LTVItemMany<ED> for VecDeque<T> {
+    type Item = T;
+    fn new() -> Self {
+        VecDeque::new()
+    }
+    fn add_item(&mut self, _field_id: u8, v: Self::Item) -> LTVResult<()> {
+        self.push_back(v);
+        Ok(())
+    }
+    fn get_items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+impl<T: LTVItem<ED>, const ED: ByteOrder> LTVItemMany<ED> for Box<[T]> {
+    type Item = T;
+    fn new() -> Self {
+        Box::default()
+    }
+    /// Reallocates for every item, decoding goes through `from_items` instead.
+    fn add_item(&mut self, _field_id: u8, v: Self::Item) -> LTVResult<()> {
+        let mut items = std::mem::take(self).into_vec();
+        items.push(v);
+        *self = items.into_boxed_slice();
+        Ok(())
+    }
+    fn get_items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+    /// Collects into a `Vec` and boxes it once.
+    fn from_items(_field_id: u8, items: impl Iterator<Item = LTVResult<Self::Item>>) -> LTVResult<Self> {
+        Ok(items.collect::<LTVResult<Vec<_>>>()?.into_boxed_slice())
+    }
+}
+
+impl<T: LTVItem<ED> + Ord, const ED: ByteOrder> LTVItemMany<ED> for BTreeSet<T> {
+    type Item = T;
+    fn new() -> Self {
+        BTreeSet::new()
+    }
+    /// Repeated items fail with `LTVError::DuplicateKey`.
+    fn add_item(&mut self, field_id: u8, v: Self::Item) -> LTVResult<()> {
+        if !self.insert(v) {
+            return Err(LTVError::DuplicateKey(field_id));
+        }
+        Ok(())
+    }
+    fn get_items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+impl<T, S, const ED: ByteOrder> LTVItemMany<ED> for HashSet<T, S>
+where
+    T: LTVItem<ED> + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Item = T;
+    const UNORDERED: bool = true;
+    fn new() -> Self {
+        HashSet::default()
+    }
+    /// Repeated items fail with `LTVError::DuplicateKey`.
+    fn add_item(&mut self, field_id: u8, v: Self::Item) -> LTVResult<()> {
+        if !self.insert(v) {
+            return Err(LTVError::DuplicateKey(field_id));
+        }
+        Ok(())
+    }
+    fn get_items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+/// Maps, written as one field per entry holding the key and the value as nested fields.
+pub trait LTVItemMap<const ED: ByteOrder>: Sized {
+    type Key: LTVItem<ED>;
+    type Value: LTVItem<ED>;
+    /// `true` if the iteration order is not deterministic, canonical writers sort these entries.
+    const UNORDERED: bool = false;
+    fn new() -> Self;
+    /// Returns `false` if the map already has `key`.
+    fn insert_entry(&mut self, key: Self::Key, value: Self::Value) -> bool;
+    fn get_entries(&self) -> impl Iterator<Item = (&Self::Key, &Self::Value)>;
+}
+
+impl<K, V, S, const ED: ByteOrder> LTVItemMap<ED> for HashMap<K, V, S>
+where
+    K: LTVItem<ED> + Eq + Hash,
+    V: LTVItem<ED>,
+    S: BuildHasher + Default,
+{
+    type Key = K;
+    type Value = V;
+    const UNORDERED: bool = true;
+    fn new() -> Self {
+        HashMap::default()
+    }
+    fn insert_entry(&mut self, key: K, value: V) -> bool {
+        match self.entry(key) {
+            hash_map::Entry::Vacant(e) => {
+                e.insert(value);
+                true
+            }
+            hash_map::Entry::Occupied(_) => false,
+        }
+    }
+    fn get_entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+}
+
+impl<K: LTVItem<ED> + Ord, V: LTVItem<ED>, const ED: ByteOrder> LTVItemMap<ED> for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+    fn new() -> Self {
+        BTreeMap::new()
+    }
+    fn insert_entry(&mut self, key: K, value: V) -> bool {
+        match self.entry(key) {
+            btree_map::Entry::Vacant(e) => {
+                e.insert(value);
+                true
+            }
+            btree_map::Entry::Occupied(_) => false,
+        }
+    }
+    fn get_entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
     }
 }
 
@@ -43,7 +244,16 @@ pub trait LTVObjectConvertable<'a, const ED: ByteOrder, const LENGTH_BYTE: usize
     LTVItem<ED>
 {
     fn from_ltv_object(data: &'a [u8]) -> LTVResult<Self>;
-    fn to_ltv_object(&self) -> Vec<u8>;
+    fn try_to_ltv_object(&self) -> LTVResult<Vec<u8>>;
+
+    /// Panics if the object can not be encoded, see `try_to_ltv_object`.
+    fn to_ltv_object(&self) -> Vec<u8> {
+        self.try_to_ltv_object()
+            .expect("failed to encode LTV object, use `try_to_ltv_object` to handle the error")
+    }
+
+    /// Writes the whole object (`[L][T][V]`) into `buf`, see `LTVItem::encode_ltv`.
+    fn encode_ltv_object(&self, buf: &mut [u8]) -> LTVResult<usize>;
 
      // LTV without the [L]ength
      fn from_ltv_object_body(data: &'a [u8])-> LTVResult<Self>{
@@ -70,28 +280,31 @@ impl<
         Ok(Self::from_ltv(obj_id, data)?)
     }
 
-    fn to_ltv_object(&self) -> Vec<u8> {
-        let mut data = self.to_ltv();
-        let body_length = data.len() + 1;
-        let mut out_ltv = Vec::with_capacity(LENGTH_BYTE + body_length);
-
-        match LENGTH_BYTE {
-            1 => out_ltv.push(body_length as u8),
-            2 => {
-                let lengthu16 = body_length as u16;
-                let bytes = match ED {
-                    ByteOrder::BE => lengthu16.to_be_bytes(),
-                    ByteOrder::LE => lengthu16.to_le_bytes(),
-                };
-                out_ltv.extend_from_slice(&bytes);
-            }
-            _ => panic!("Unsupported length byte {}", LENGTH_BYTE),
-        };
+    fn try_to_ltv_object(&self) -> LTVResult<Vec<u8>> {
+        let mut data = self.try_to_ltv()?;
+        let (header, header_size) = encode_header::<ED, LENGTH_BYTE>(Self::OBJECT_ID, data.len())?;
+        let mut out_ltv = Vec::with_capacity(header_size + data.len());
 
-        out_ltv.push(Self::OBJECT_ID);
+        out_ltv.extend_from_slice(&header[..header_size]);
         out_ltv.append(&mut data);
 
-        out_ltv
+        Ok(out_ltv)
+    }
+
+    fn encode_ltv_object(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        let header_size = LENGTH_BYTE + 1;
+        let data_length = encode_value(self, buf.get_mut(header_size..).unwrap_or_default())?;
+        let (header, _) = encode_header::<ED, LENGTH_BYTE>(Self::OBJECT_ID, data_length)?;
+
+        let required = header_size + data_length;
+        if required > buf.len() {
+            return Err(LTVError::BufferTooSmall {
+                required,
+                available: buf.len(),
+            });
+        }
+        buf[..header_size].copy_from_slice(&header[..header_size]);
+        Ok(required)
     }
 }
 
@@ -107,9 +320,17 @@ impl<'a, const ED: ByteOrder> LTVItem<ED> for () {
         Vec::new()
     }
 
+    fn encode_ltv(&self, _: &mut [u8]) -> LTVResult<usize> {
+        Ok(0)
+    }
+
     fn not_found(_: u8) -> LTVResult<Self> {
         Ok(())
     }
+
+    fn is_present(&self) -> bool {
+        false
+    }
 }
 
 impl<'a, T: LTVItem<ED>, const ED: ByteOrder> LTVItem<ED> for Option<T> {
@@ -117,6 +338,11 @@ impl<'a, T: LTVItem<ED>, const ED: ByteOrder> LTVItem<ED> for Option<T> {
         Ok(Some(T::from_ltv(field_id, data)?))
     }
 
+    #[cfg(feature = "bytes")]
+    fn from_ltv_bytes(field_id: u8, data: ::bytes::Bytes) -> LTVResult<Self> {
+        Ok(Some(T::from_ltv_bytes(field_id, data)?))
+    }
+
     fn to_ltv(&self) -> Vec<u8> {
         if let Some(e) = self {
             e.to_ltv()
@@ -125,9 +351,36 @@ impl<'a, T: LTVItem<ED>, const ED: ByteOrder> LTVItem<ED> for Option<T> {
         }
     }
 
+    fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+        if let Some(e) = self {
+            e.try_to_ltv()
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        if let Some(e) = self {
+            e.encode_ltv(buf)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Some(e) => e.as_ltv_bytes(),
+            None => Some(&[]),
+        }
+    }
+
     fn not_found(_: u8) -> LTVResult<Self> {
         Ok(None)
     }
+
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
 }
 
 impl<const ED: ByteOrder> LTVItem<ED> for Vec<u8> {
@@ -138,9 +391,19 @@ impl<const ED: ByteOrder> LTVItem<ED> for Vec<u8> {
     fn to_ltv(&self) -> Vec<u8> {
         self.clone()
     }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(self, buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
 }
 
 impl<const ED: ByteOrder, const LENGTH: usize> LTVItem<ED> for [u8; LENGTH] {
+    const FIXED_SIZE: Option<usize> = Some(LENGTH);
+
     fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
         data.try_into().map_err(|_| LTVError::WrongSize {
             field_id: field_id,
@@ -152,6 +415,14 @@ impl<const ED: ByteOrder, const LENGTH: usize> LTVItem<ED> for [u8; LENGTH] {
     fn to_ltv(&self) -> Vec<u8> {
         (self as &[_]).into()
     }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(self, buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
 }
 
 macro_rules! impl_numeric_ltvitem {
@@ -159,6 +430,8 @@ macro_rules! impl_numeric_ltvitem {
     $(
 
     impl<const ED: ByteOrder> LTVItem<ED> for $i {
+        const FIXED_SIZE: Option<usize> = Some(std::mem::size_of::<$i>());
+
         fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
             let numeric_value= data
                 .try_into()
@@ -171,7 +444,7 @@ macro_rules! impl_numeric_ltvitem {
                 Ok(b) => Ok(b),
                 Err(_) => Err(LTVError::WrongSize {
                     field_id: field_id,
-                    expected: ($i::BITS/8) as usize,
+                    expected: std::mem::size_of::<$i>(),
                     recieved: data.len(),
                 })
             }
@@ -184,6 +457,15 @@ macro_rules! impl_numeric_ltvitem {
                 }
             )
         }
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            copy_to_buffer(
+                &match ED {
+                    ByteOrder::LE=> $i::to_le_bytes(*self),
+                    ByteOrder::BE=> $i::to_be_bytes(*self),
+                },
+                buf
+            )
+        }
     }
 
     )*
@@ -201,5 +483,164 @@ impl_numeric_ltvitem! {
     u64, 
     i64,
     u128,
-    i128
+    i128,
+    f32,
+    f64
+}
+
+/// Checks that `data` holds a whole number of `size` byte elements.
+fn check_packed_len(field_id: u8, data: &[u8], size: usize) -> LTVResult<()> {
+    if !data.len().is_multiple_of(size) {
+        return Err(LTVError::WrongSize {
+            field_id,
+            expected: (data.len() / size + 1) * size,
+            recieved: data.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Writes each element of a packed array/vector next to each other.
+fn encode_packed<T: LTVItem<ED>, const ED: ByteOrder>(values: &[T], buf: &mut [u8]) -> LTVResult<usize> {
+    let size = std::mem::size_of::<T>();
+    let required = std::mem::size_of_val(values);
+    if buf.len() < required {
+        return Err(LTVError::BufferTooSmall {
+            required,
+            available: buf.len(),
+        });
+    }
+    for (v, out) in values.iter().zip(buf.chunks_exact_mut(size)) {
+        v.encode_ltv(out)?;
+    }
+    Ok(required)
+}
+
+// Packed arrays/vectors of numbers, each element in the field byte order.
+// `Vec<u8>` and `[u8; N]` are implemented above as raw bytes.
+macro_rules! impl_packed_numeric_ltvitem {
+    ($($i:ident),+) => {
+    $(
+
+    impl<const ED: ByteOrder> LTVItem<ED> for Vec<$i> {
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            const SIZE: usize = std::mem::size_of::<$i>();
+            check_packed_len(field_id, data, SIZE)?;
+            Ok(data
+                .chunks_exact(SIZE)
+                .map(|b| {
+                    let b = b.try_into().unwrap();
+                    match ED {
+                        ByteOrder::LE => $i::from_le_bytes(b),
+                        ByteOrder::BE => $i::from_be_bytes(b),
+                    }
+                })
+                .collect())
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            self.iter()
+                .flat_map(|v| match ED {
+                    ByteOrder::LE => v.to_le_bytes(),
+                    ByteOrder::BE => v.to_be_bytes(),
+                })
+                .collect()
+        }
+
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            encode_packed::<$i, ED>(self, buf)
+        }
+    }
+
+    impl<const ED: ByteOrder, const LENGTH: usize> LTVItem<ED> for [$i; LENGTH] {
+        const FIXED_SIZE: Option<usize> = Some(LENGTH * std::mem::size_of::<$i>());
+
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            const SIZE: usize = std::mem::size_of::<$i>();
+            if data.len() != LENGTH * SIZE {
+                return Err(LTVError::WrongSize {
+                    field_id,
+                    expected: LENGTH * SIZE,
+                    recieved: data.len(),
+                });
+            }
+            let mut out = [$i::default(); LENGTH];
+            for (v, b) in out.iter_mut().zip(data.chunks_exact(SIZE)) {
+                let b = b.try_into().unwrap();
+                *v = match ED {
+                    ByteOrder::LE => $i::from_le_bytes(b),
+                    ByteOrder::BE => $i::from_be_bytes(b),
+                };
+            }
+            Ok(out)
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            self.iter()
+                .flat_map(|v| match ED {
+                    ByteOrder::LE => v.to_le_bytes(),
+                    ByteOrder::BE => v.to_be_bytes(),
+                })
+                .collect()
+        }
+
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            encode_packed::<$i, ED>(self, buf)
+        }
+    }
+
+    )*
+
+    };
+}
+
+impl_packed_numeric_ltvitem! {
+    i8,
+    u16,
+    i16,
+    u32,
+    i32,
+    u64,
+    i64,
+    u128,
+    i128,
+    f32,
+    f64
+}
+
+impl<const ED: ByteOrder> LTVItem<ED> for bool {
+    const FIXED_SIZE: Option<usize> = Some(1);
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        match <u8 as LTVItem<ED>>::from_ltv(field_id, data)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(LTVError::InvalidBool { field_id, value }),
+        }
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        vec![*self as u8]
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(&[*self as u8], buf)
+    }
+}
+
+impl<const ED: ByteOrder> LTVItem<ED> for char {
+    const FIXED_SIZE: Option<usize> = Some(4);
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        let value = <u32 as LTVItem<ED>>::from_ltv(field_id, data)?;
+        char::from_u32(value).ok_or(LTVError::InvalidChar { field_id, value })
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        <u32 as LTVItem<ED>>::to_ltv(&(*self as u32))
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        <u32 as LTVItem<ED>>::encode_ltv(&(*self as u32), buf)
+    }
 }
@@ -2,7 +2,8 @@ use std::marker::PhantomData;
 
 use crate::{
     error::{LTVError, LTVResult},
-    ByteOrder, LTVItem, LTVItemMany,
+    object::copy_to_buffer,
+    ByteOrder, LTVItem, LTVItemMany, LTVItemMap,
 };
 
 pub struct LTVFieldIterator<'a, T: LTVItem<ED>, const ED: ByteOrder, const LENGTH_SIZE: usize> {
@@ -56,7 +57,15 @@ impl<'a, const ED: ByteOrder> LTVItem<ED> for LTVFieldBinary {
         })
     }
     fn to_ltv(&self) -> Vec<u8> {
-        unimplemented!()
+        self.data.clone()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(&self.data, buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(&self.data)
     }
 }
 
@@ -96,16 +105,55 @@ impl<'a, const ED: ByteOrder, const LENGTH_SIZE: usize> LTVReader<'a, ED, LENGTH
     }
 
     pub fn get_many<T: LTVItem<ED>, M: LTVItemMany<ED>>(&self, field_id: u8) -> LTVResult<M> {
-        let mut v = M::new();
+        let items = self.iter::<LTVFieldBinary>().filter_map(|o| match o {
+            Ok(binary_field) if binary_field.field_id == field_id => {
+                Some(M::Item::from_ltv(field_id, &binary_field.data))
+            }
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        });
+        M::from_items(field_id, items)
+    }
+
+    /// Reads every `field_id` field as a map entry with the key in field `key_id`
+    /// and the value in field `value_id`. Repeated keys fail with `LTVError::DuplicateKey`.
+    pub fn get_map<M: LTVItemMap<ED>>(&self, field_id: u8, key_id: u8, value_id: u8) -> LTVResult<M> {
+        let mut map = M::new();
         for o in self.iter::<LTVFieldBinary>() {
             let binary_field = o?;
 
             if binary_field.field_id == field_id {
-                let o = M::Item::from_ltv(field_id, &binary_field.data)?;
-                v.add_item(o);
+                let entry = LTVReader::<ED, LENGTH_SIZE>::new(&binary_field.data);
+                let key = entry.get_item::<M::Key>(key_id)?;
+                let value = entry.get_item::<M::Value>(value_id)?;
+                if !map.insert_entry(key, value) {
+                    return Err(LTVError::DuplicateKey(field_id));
+                }
+            }
+        }
+        Ok(map)
+    }
+
+    /// The id and value of the only field in `field_ids` that is present.
+    ///
+    /// Fails with `LTVError::OneOf` if none or more than one of them are present.
+    pub fn get_one_of(&self, field_id: u8, field_ids: &[u8]) -> LTVResult<(u8, &'a [u8])> {
+        let mut found = None;
+        let mut count = 0;
+        let mut i = 0;
+        while i < self.body.len() {
+            let (length, ltv_id, data) = Self::parse_ltv(&self.body[i..])?;
+            // zero length padding has no tag
+            if length > LENGTH_SIZE && field_ids.contains(&ltv_id) {
+                found.get_or_insert((ltv_id, data));
+                count += 1;
             }
+            i += length;
+        }
+        match found {
+            Some(field) if count == 1 => Ok(field),
+            _ => Err(LTVError::OneOf { field_id, found: count }),
         }
-        Ok(v)
     }
 
     pub fn parse_ltv<'b>(data: &'b [u8]) -> LTVResult<(usize, u8, &'b [u8])> {
@@ -143,6 +191,31 @@ impl<'a, const ED: ByteOrder, const LENGTH_SIZE: usize> LTVReader<'a, ED, LENGTH
         Ok((expected_length, field_type, ltv_data))
     }
 
+    /// Checks that `data` is a canonical body, as written by `LTVWriter::canonical`:
+    /// every field is well formed, there is no zero length padding or trailing data
+    /// and the tags are in ascending order.
+    ///
+    /// Only the top level fields are checked, nested values need to be checked separately.
+    pub fn is_canonical(data: &[u8]) -> bool {
+        let mut i = 0;
+        let mut last_id = None;
+        while i < data.len() {
+            let (length, field_id, _) = match Self::parse_ltv(&data[i..]) {
+                Ok(v) => v,
+                Err(_) => return false,
+            };
+            if length == LENGTH_SIZE {
+                return false;
+            }
+            if matches!(last_id, Some(previous) if field_id < previous) {
+                return false;
+            }
+            last_id = Some(field_id);
+            i += length;
+        }
+        true
+    }
+
     pub fn get_field<T: LTVItem<ED>>(data: &'a [u8], field_id: u8) -> LTVResult<T> {
         let r = Self::new(data);
         match r.get_item_optional::<T>(field_id)? {
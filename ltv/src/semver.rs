@@ -0,0 +1,37 @@
+use ::semver::Version;
+
+use crate::{
+    error::{LTVError, LTVResult},
+    ByteOrder, LTVItem,
+};
+
+/// The version string, e.g. `1.2.3-beta.1`.
+impl<const ED: ByteOrder> LTVItem<ED> for Version {
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        let text = <String as LTVItem<ED>>::from_ltv(field_id, data)?;
+        Version::parse(&text).map_err(|e| LTVError::InvalidText(field_id, format!("{}", e)))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::semver::Version;
+
+    use crate::*;
+
+    #[test]
+    fn semver_version() {
+        let version = Version::parse("1.2.3-beta.1").unwrap();
+        let data = <Version as LTVItem<{ ByteOrder::BE }>>::to_ltv(&version);
+        assert_eq!(data, b"1.2.3-beta.1".to_vec());
+        assert_eq!(<Version as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data).unwrap(), version);
+        assert!(matches!(
+            <Version as LTVItem<{ ByteOrder::BE }>>::from_ltv(2, b"1.2"),
+            Err(LTVError::InvalidText(2, _))
+        ));
+    }
+}
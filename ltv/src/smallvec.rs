@@ -0,0 +1,44 @@
+use ::smallvec::{Array, SmallVec};
+
+use crate::{error::LTVResult, ByteOrder, LTVItem, LTVItemMany};
+
+impl<A: Array, const ED: ByteOrder> LTVItemMany<ED> for SmallVec<A>
+where
+    A::Item: LTVItem<ED>,
+{
+    type Item = A::Item;
+    fn new() -> Self {
+        SmallVec::new()
+    }
+    fn add_item(&mut self, _field_id: u8, v: Self::Item) -> LTVResult<()> {
+        self.push(v);
+        Ok(())
+    }
+    fn get_items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::smallvec::SmallVec;
+
+    use crate::*;
+
+    #[test]
+    fn small_vec_spills() {
+        let data = [2, 1, 0x0A, 2, 2, 0xFF, 2, 1, 0x0B, 2, 1, 0x0C];
+        let reader = LTVReaderBE::<1>::new(&data);
+
+        let items = reader.get_many::<u8, SmallVec<[u8; 2]>>(1).unwrap();
+        assert_eq!(&items[..], &[0x0A, 0x0B, 0x0C]);
+        assert!(items.spilled());
+
+        let mut writer = LTVWriterBE::<_, 1>::new(Vec::new());
+        writer.write_ltv_list(1, &items).unwrap();
+        assert_eq!(
+            writer.into_inner(),
+            vec![2, 1, 0x0A, 2, 1, 0x0B, 2, 1, 0x0C]
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::{borrow::Cow, ops::Deref};
+
+use crate::{
+    error::{LTVError, LTVResult},
+    object::copy_to_buffer,
+    ByteOrder, LTVItem,
+};
+
+fn from_utf8(field_id: u8, data: &[u8]) -> LTVResult<String> {
+    std::str::from_utf8(data)
+        .map(String::from)
+        .map_err(|error| LTVError::InvalidUtf8 { field_id, error })
+}
+
+impl<const ED: ByteOrder> LTVItem<ED> for String {
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        from_utf8(field_id, data)
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(self.as_bytes(), buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(self.as_bytes())
+    }
+}
+
+impl<const ED: ByteOrder> LTVItem<ED> for Box<str> {
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        Ok(from_utf8(field_id, data)?.into_boxed_str())
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(self.as_bytes(), buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(self.as_bytes())
+    }
+}
+
+/// Always decodes to `Cow::Owned`.
+impl<'a, const ED: ByteOrder> LTVItem<ED> for Cow<'a, str> {
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        Ok(Cow::Owned(from_utf8(field_id, data)?))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(self.as_bytes(), buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(self.as_bytes())
+    }
+}
+
+macro_rules! impl_text_wrapper {
+    ($($name:ident),+) => {
+    $(
+    impl Deref for $name {
+        type Target = str;
+        fn deref(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl From<String> for $name {
+        fn from(s: String) -> Self {
+            Self(s)
+        }
+    }
+
+    impl From<&str> for $name {
+        fn from(s: &str) -> Self {
+            Self(s.into())
+        }
+    }
+
+    impl From<$name> for String {
+        fn from(s: $name) -> Self {
+            s.0
+        }
+    }
+    )*
+    };
+}
+
+/// UTF-8 string followed by a NUL byte, like a C string.
+///
+/// Decoding stops at the first NUL, anything after it is treated as padding.
+/// Strings containing a NUL can not be encoded.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NulString(pub String);
+
+/// ISO-8859-1 string, one byte per character.
+/// Strings with characters above U+00FF can not be encoded.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Latin1(pub String);
+
+/// UTF-16 string in the byte order of the field.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Utf16(pub String);
+
+impl_text_wrapper! {
+    NulString,
+    Latin1,
+    Utf16
+}
+
+impl<const ED: ByteOrder> LTVItem<ED> for NulString {
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        match data.iter().position(|b| *b == 0) {
+            Some(end) => Ok(Self(from_utf8(field_id, &data[..end])?)),
+            None => Err(LTVError::InvalidText(
+                field_id,
+                "missing NUL terminator".into(),
+            )),
+        }
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        <Self as LTVItem<ED>>::try_to_ltv(self)
+            .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+    }
+
+    fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+        if self.0.contains('\0') {
+            return Err(LTVError::EncodeError(format!(
+                "{:?} contains a NUL byte",
+                self.0
+            )));
+        }
+        let mut data = Vec::with_capacity(self.0.len() + 1);
+        data.extend_from_slice(self.0.as_bytes());
+        data.push(0);
+        Ok(data)
+    }
+}
+
+impl<const ED: ByteOrder> LTVItem<ED> for Latin1 {
+    fn from_ltv(_field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        Ok(Self(data.iter().map(|b| *b as char).collect()))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        <Self as LTVItem<ED>>::try_to_ltv(self)
+            .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+    }
+
+    fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+        self.0
+            .chars()
+            .map(|c| {
+                if (c as u32) <= 0xFF {
+                    Ok(c as u8)
+                } else {
+                    Err(LTVError::EncodeError(format!(
+                        "{:?} can not be encoded as Latin-1",
+                        c
+                    )))
+                }
+            })
+            .collect()
+    }
+}
+
+impl<const ED: ByteOrder> LTVItem<ED> for Utf16 {
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        if !data.len().is_multiple_of(2) {
+            return Err(LTVError::InvalidText(
+                field_id,
+                "UTF-16 data has an odd length".into(),
+            ));
+        }
+        let units = data.chunks_exact(2).map(|b| match ED {
+            ByteOrder::LE => u16::from_le_bytes([b[0], b[1]]),
+            ByteOrder::BE => u16::from_be_bytes([b[0], b[1]]),
+        });
+        char::decode_utf16(units)
+            .collect::<Result<String, _>>()
+            .map(Self)
+            .map_err(|e| LTVError::InvalidText(field_id, format!("{}", e)))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.0
+            .encode_utf16()
+            .flat_map(|u| match ED {
+                ByteOrder::LE => u.to_le_bytes(),
+                ByteOrder::BE => u.to_be_bytes(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,198 @@
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    error::{LTVError, LTVResult},
+    ByteOrder, LTVItem,
+};
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// Times and durations that can be written as a number of time units.
+pub trait TimeValue: Sized {
+    /// Durations are written as is, points in time relative to the epoch.
+    const IS_DURATION: bool = false;
+
+    /// Nanoseconds since the Unix epoch, or the length of a duration.
+    fn to_unix_nanos(&self) -> i128;
+
+    /// `None` if `nanos` can not be represented.
+    fn from_unix_nanos(nanos: i128) -> Option<Self>;
+}
+
+impl TimeValue for Duration {
+    const IS_DURATION: bool = true;
+
+    fn to_unix_nanos(&self) -> i128 {
+        self.as_nanos() as i128
+    }
+
+    fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        let secs = u64::try_from(nanos / NANOS_PER_SEC).ok()?;
+        Some(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+    }
+}
+
+impl TimeValue for SystemTime {
+    fn to_unix_nanos(&self) -> i128 {
+        match self.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.to_unix_nanos(),
+            Err(before) => -before.duration().to_unix_nanos(),
+        }
+    }
+
+    fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        if nanos >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_unix_nanos(nanos)?)
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_unix_nanos(-nanos)?)
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TimeValue for ::chrono::DateTime<::chrono::Utc> {
+    fn to_unix_nanos(&self) -> i128 {
+        self.timestamp() as i128 * NANOS_PER_SEC + self.timestamp_subsec_nanos() as i128
+    }
+
+    fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+        Self::from_timestamp(secs, nanos.rem_euclid(NANOS_PER_SEC) as u32)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TimeValue for ::time::OffsetDateTime {
+    fn to_unix_nanos(&self) -> i128 {
+        self.unix_timestamp_nanos()
+    }
+
+    fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        Self::from_unix_timestamp_nanos(nanos).ok()
+    }
+}
+
+/// A time written as an unsigned integer of `WIDTH` bytes (4 or 8) counting `UNITS_PER_SEC` units,
+/// e.g. `Time<SystemTime, 1, 4>` for 32 bit seconds since the Unix epoch.
+///
+/// Points in time are counted from `EPOCH`, given in seconds since the Unix epoch.
+/// Values that are before the epoch or do not fit fail with `LTVError::EncodeError`,
+/// anything below the unit is truncated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Time<T, const UNITS_PER_SEC: u64 = 1000, const WIDTH: usize = 8, const EPOCH: i64 = 0>(
+    pub T,
+);
+
+impl<T, const UNITS_PER_SEC: u64, const WIDTH: usize, const EPOCH: i64> From<T>
+    for Time<T, UNITS_PER_SEC, WIDTH, EPOCH>
+{
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: TimeValue, const UNITS_PER_SEC: u64, const WIDTH: usize, const EPOCH: i64>
+    Time<T, UNITS_PER_SEC, WIDTH, EPOCH>
+{
+    const VALID: () = assert!(
+        (UNITS_PER_SEC as i128) > 0
+            && NANOS_PER_SEC % (UNITS_PER_SEC as i128) == 0
+            && (WIDTH == 4 || WIDTH == 8),
+        "UNITS_PER_SEC must divide a second into whole nanoseconds and WIDTH must be 4 or 8"
+    );
+    const NANOS_PER_UNIT: i128 = NANOS_PER_SEC / UNITS_PER_SEC as i128;
+
+    fn epoch_nanos() -> i128 {
+        if T::IS_DURATION {
+            0
+        } else {
+            EPOCH as i128 * NANOS_PER_SEC
+        }
+    }
+}
+
+impl<
+        T: TimeValue,
+        const ED: ByteOrder,
+        const UNITS_PER_SEC: u64,
+        const WIDTH: usize,
+        const EPOCH: i64,
+    > LTVItem<ED> for Time<T, UNITS_PER_SEC, WIDTH, EPOCH>
+{
+    const FIXED_SIZE: Option<usize> = Some(WIDTH);
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        let () = Self::VALID;
+        let units = match WIDTH {
+            4 => <u32 as LTVItem<ED>>::from_ltv(field_id, data)? as i128,
+            _ => <u64 as LTVItem<ED>>::from_ltv(field_id, data)? as i128,
+        };
+        T::from_unix_nanos(units * Self::NANOS_PER_UNIT + Self::epoch_nanos())
+            .map(Self)
+            .ok_or_else(|| LTVError::UnexpectedValue(field_id, format!("time {} is out of range", units)))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        <Self as LTVItem<ED>>::try_to_ltv(self)
+            .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+    }
+
+    fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+        let () = Self::VALID;
+        let nanos = self.0.to_unix_nanos() - Self::epoch_nanos();
+        if nanos < 0 {
+            return Err(LTVError::EncodeError("time is before the epoch".into()));
+        }
+        let units = nanos / Self::NANOS_PER_UNIT;
+        let overflow = || LTVError::EncodeError(format!("time {} does not fit in {} bytes", units, WIDTH));
+        match WIDTH {
+            4 => Ok(<u32 as LTVItem<ED>>::to_ltv(&u32::try_from(units).map_err(|_| overflow())?)),
+            _ => Ok(<u64 as LTVItem<ED>>::to_ltv(&u64::try_from(units).map_err(|_| overflow())?)),
+        }
+    }
+}
+
+/// Plain times are written as `Time<T>`: 64 bit milliseconds since the Unix epoch.
+///
+/// Anything below a millisecond is truncated, so e.g. `Duration::from_micros(1500)` decodes
+/// as `Duration::from_millis(1)`. Use `Time<T, 1_000_000_000>` or `#[ltv_field(id, unit = "ns")]` to keep it.
+macro_rules! impl_time_ltvitem {
+    ($($t:ty),+) => {
+    $(
+
+    impl<const ED: ByteOrder> LTVItem<ED> for $t {
+        const FIXED_SIZE: Option<usize> = Some(8);
+
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            Ok(<Time<$t> as LTVItem<ED>>::from_ltv(field_id, data)?.0)
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            <Self as LTVItem<ED>>::try_to_ltv(self)
+                .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+        }
+
+        fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+            <Time<$t> as LTVItem<ED>>::try_to_ltv(&Time(*self))
+        }
+    }
+
+    )*
+    };
+}
+
+impl_time_ltvitem! {
+    Duration,
+    SystemTime
+}
+
+#[cfg(feature = "chrono")]
+impl_time_ltvitem! {
+    ::chrono::DateTime<::chrono::Utc>
+}
+
+#[cfg(feature = "time")]
+impl_time_ltvitem! {
+    ::time::OffsetDateTime
+}
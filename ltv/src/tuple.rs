@@ -0,0 +1,169 @@
+use crate::{
+    error::{LTVError, LTVResult},
+    reader::LTVReader,
+    writer::{encode_value, LTVContainer, LTVSliceWriter, LTVWriter},
+    ByteOrder, LTVItem,
+};
+
+/// Tuple whose members are written as nested fields with tags by position (1, 2, 3, ...)
+/// instead of being concatenated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Positional<T, const LENGTH_SIZE: usize = 1>(pub T);
+
+impl<T, const LENGTH_SIZE: usize> From<T> for Positional<T, LENGTH_SIZE> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+const fn fixed_size_sum(sizes: &[Option<usize>]) -> Option<usize> {
+    let mut total = 0;
+    let mut i = 0;
+    while i < sizes.len() {
+        match sizes[i] {
+            Some(size) => total += size,
+            None => return None,
+        }
+        i += 1;
+    }
+    Some(total)
+}
+
+/// The size of the members before the last one, which must all be fixed.
+/// Evaluated at compile time, so a variable size member fails the build where the tuple is decoded.
+const fn head_size(sizes: &[Option<usize>]) -> usize {
+    match fixed_size_sum(sizes) {
+        Some(size) => size,
+        None => panic!("only the last tuple member can have a variable size"),
+    }
+}
+
+/// Hands out consecutive slices of a tuple value.
+struct Members<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Members<'a> {
+    fn next(&mut self, size: usize) -> &'a [u8] {
+        let (member, rest) = self.data.split_at(size);
+        self.data = rest;
+        member
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        std::mem::take(&mut self.data)
+    }
+}
+
+/// Tuples are written as their members concatenated in one value.
+/// Every member but the last must have a `FIXED_SIZE`, the last one gets the rest of the value.
+///
+/// A variable size member anywhere else (e.g. `(String, u8)`) can not be split again,
+/// so decoding such a tuple is a compile time error: "only the last tuple member can have a variable size".
+/// The error points into this crate, not at the field, use `Positional` for these tuples.
+macro_rules! impl_tuple_ltvitem {
+    ($(($($t:ident $n:tt),*; $last:ident $last_n:tt)),+) => {
+    $(
+
+    impl<const ED: ByteOrder, $($t: LTVItem<ED>,)* $last: LTVItem<ED>> LTVItem<ED> for ($($t,)* $last,) {
+        const FIXED_SIZE: Option<usize> = fixed_size_sum(&[
+            $(<$t as LTVItem<ED>>::FIXED_SIZE,)*
+            <$last as LTVItem<ED>>::FIXED_SIZE
+        ]);
+
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            let head_size = const { head_size(&[$(<$t as LTVItem<ED>>::FIXED_SIZE),*]) };
+            let size_ok = match <$last as LTVItem<ED>>::FIXED_SIZE {
+                Some(last_size) => data.len() == head_size + last_size,
+                None => data.len() >= head_size,
+            };
+            if !size_ok {
+                return Err(LTVError::WrongSize {
+                    field_id,
+                    expected: head_size + <$last as LTVItem<ED>>::FIXED_SIZE.unwrap_or(0),
+                    recieved: data.len(),
+                });
+            }
+
+            let mut members = Members { data };
+            Ok((
+                $(<$t as LTVItem<ED>>::from_ltv(
+                    field_id,
+                    members.next(<$t as LTVItem<ED>>::FIXED_SIZE.unwrap_or(0)),
+                )?,)*
+                <$last as LTVItem<ED>>::from_ltv(field_id, members.rest())?,
+            ))
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            <Self as LTVItem<ED>>::try_to_ltv(self)
+                .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+        }
+
+        fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+            let mut data = Vec::with_capacity(<Self as LTVItem<ED>>::FIXED_SIZE.unwrap_or(0));
+            $(data.extend_from_slice(&self.$n.try_to_ltv()?);)*
+            data.extend_from_slice(&self.$last_n.try_to_ltv()?);
+            Ok(data)
+        }
+
+        /// Members that do not fit are still counted, so `LTVError::BufferTooSmall` has the full size.
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            let mut position = 0;
+            $(position += encode_value(&self.$n, buf.get_mut(position..).unwrap_or_default())?;)*
+            position += encode_value(&self.$last_n, buf.get_mut(position..).unwrap_or_default())?;
+            if position > buf.len() {
+                return Err(LTVError::BufferTooSmall {
+                    required: position,
+                    available: buf.len(),
+                });
+            }
+            Ok(position)
+        }
+    }
+
+    impl<const ED: ByteOrder, const LENGTH_SIZE: usize, $($t: LTVItem<ED>,)* $last: LTVItem<ED>> LTVItem<ED>
+        for Positional<($($t,)* $last,), LENGTH_SIZE>
+    {
+        fn from_ltv(_field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            let reader = LTVReader::<ED, LENGTH_SIZE>::new(data);
+            Ok(Self((
+                $(reader.get_item::<$t>($n + 1)?,)*
+                reader.get_item::<$last>($last_n + 1)?,
+            )))
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            <Self as LTVItem<ED>>::try_to_ltv(self)
+                .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+        }
+
+        fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+            let mut buffer = LTVWriter::<_, ED, LENGTH_SIZE>::new(Vec::new());
+            $(buffer.write_ltv($n + 1, &self.0.$n)?;)*
+            buffer.write_ltv($last_n + 1, &self.0.$last_n)?;
+            Ok(buffer.into_inner())
+        }
+
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            let mut buffer = LTVSliceWriter::<ED, LENGTH_SIZE>::new(buf);
+            $(buffer.write_ltv($n + 1, &self.0.$n)?;)*
+            buffer.write_ltv($last_n + 1, &self.0.$last_n)?;
+            buffer.finish()
+        }
+    }
+
+    )*
+    };
+}
+
+impl_tuple_ltvitem! {
+    (; A 0),
+    (A 0; B 1),
+    (A 0, B 1; C 2),
+    (A 0, B 1, C 2; D 3),
+    (A 0, B 1, C 2, D 3; E 4),
+    (A 0, B 1, C 2, D 3, E 4; F 5),
+    (A 0, B 1, C 2, D 3, E 4, F 5; G 6),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6; H 7)
+}
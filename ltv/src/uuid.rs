@@ -0,0 +1,40 @@
+use ::uuid::Uuid;
+
+use crate::{error::LTVResult, object::copy_to_buffer, ByteOrder, LTVItem};
+
+/// The 16 bytes of the UUID in their standard (big endian) order, whatever the field byte order.
+impl<const ED: ByteOrder> LTVItem<ED> for Uuid {
+    const FIXED_SIZE: Option<usize> = Some(16);
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        Ok(Uuid::from_bytes(<[u8; 16] as LTVItem<ED>>::from_ltv(field_id, data)?))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(self.as_bytes(), buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(self.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::uuid::Uuid;
+
+    use crate::*;
+
+    #[test]
+    fn uuid_bytes() {
+        let id = Uuid::from_u128(0x0102030405060708090A0B0C0D0E0F10);
+        let data = <Uuid as LTVItem<{ ByteOrder::LE }>>::to_ltv(&id);
+        assert_eq!(data, (1..=16).collect::<Vec<u8>>());
+        assert_eq!(<Uuid as LTVItem<{ ByteOrder::LE }>>::from_ltv(0, &data).unwrap(), id);
+        assert!(<Uuid as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &data[1..]).is_err());
+    }
+}
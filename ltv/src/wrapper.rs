@@ -0,0 +1,111 @@
+use std::{borrow::Cow, num::Wrapping, rc::Rc, sync::Arc};
+
+use crate::{error::LTVResult, object::copy_to_buffer, ByteOrder, LTVItem};
+
+/// Wrappers encode exactly like the value they hold.
+macro_rules! impl_forwarding_ltvitem {
+    ($($w:ident),+) => {
+    $(
+
+    impl<T: LTVItem<ED>, const ED: ByteOrder> LTVItem<ED> for $w<T> {
+        const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
+        fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+            Ok($w::new(T::from_ltv(field_id, data)?))
+        }
+
+        #[cfg(feature = "bytes")]
+        fn from_ltv_bytes(field_id: u8, data: ::bytes::Bytes) -> LTVResult<Self> {
+            Ok($w::new(T::from_ltv_bytes(field_id, data)?))
+        }
+
+        fn to_ltv(&self) -> Vec<u8> {
+            (**self).to_ltv()
+        }
+
+        fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+            (**self).try_to_ltv()
+        }
+
+        fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+            (**self).encode_ltv(buf)
+        }
+
+        fn as_ltv_bytes(&self) -> Option<&[u8]> {
+            (**self).as_ltv_bytes()
+        }
+
+        fn not_found(field_id: u8) -> LTVResult<Self> {
+            Ok($w::new(T::not_found(field_id)?))
+        }
+
+        fn is_present(&self) -> bool {
+            (**self).is_present()
+        }
+    }
+
+    )*
+    };
+}
+
+impl_forwarding_ltvitem! {
+    Box,
+    Rc,
+    Arc
+}
+
+impl<T: LTVItem<ED>, const ED: ByteOrder> LTVItem<ED> for Wrapping<T> {
+    const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
+    fn from_ltv(field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        Ok(Wrapping(T::from_ltv(field_id, data)?))
+    }
+
+    #[cfg(feature = "bytes")]
+    fn from_ltv_bytes(field_id: u8, data: ::bytes::Bytes) -> LTVResult<Self> {
+        Ok(Wrapping(T::from_ltv_bytes(field_id, data)?))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.0.to_ltv()
+    }
+
+    fn try_to_ltv(&self) -> LTVResult<Vec<u8>> {
+        self.0.try_to_ltv()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        self.0.encode_ltv(buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        self.0.as_ltv_bytes()
+    }
+
+    fn not_found(field_id: u8) -> LTVResult<Self> {
+        Ok(Wrapping(T::not_found(field_id)?))
+    }
+
+    fn is_present(&self) -> bool {
+        self.0.is_present()
+    }
+}
+
+/// Always decodes to `Cow::Owned`.
+impl<'a, const ED: ByteOrder> LTVItem<ED> for Cow<'a, [u8]> {
+    fn from_ltv(_field_id: u8, data: &[u8]) -> LTVResult<Self> {
+        Ok(Cow::Owned(data.to_vec()))
+    }
+
+    fn to_ltv(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+
+    fn encode_ltv(&self, buf: &mut [u8]) -> LTVResult<usize> {
+        copy_to_buffer(self, buf)
+    }
+
+    fn as_ltv_bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
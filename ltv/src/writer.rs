@@ -1,37 +1,195 @@
-use crate::object::LTVItem;
+use crate::error::{LTVError, LTVResult};
+use crate::object::{LTVItem, LTVItemMany, LTVItemMap};
 use crate::ByteOrder;
-use std::io;
+use std::convert::TryFrom;
+use std::io::{self, IoSlice};
+
+/// Builds the `[L][T]` header for a value of `data_length` bytes.
+/// Returns the header buffer and how many bytes of it are used.
+pub(crate) fn encode_header<const ED: ByteOrder, const LENGTH_SIZE: usize>(
+    obj_id: u8,
+    data_length: usize,
+) -> LTVResult<([u8; 3], usize)> {
+    let body_length = data_length + 1;
+    let max_length = (1usize << (8 * LENGTH_SIZE)) - 2;
+    let overflow = || LTVError::LengthOverflow {
+        field_id: obj_id,
+        length: data_length,
+        max: max_length,
+    };
+
+    let mut header = [0u8; 3];
+    match LENGTH_SIZE {
+        1 => header[0] = u8::try_from(body_length).map_err(|_| overflow())?,
+        2 => {
+            let length = u16::try_from(body_length).map_err(|_| overflow())?;
+            header[..2].copy_from_slice(&match ED {
+                ByteOrder::LE => length.to_le_bytes(),
+                ByteOrder::BE => length.to_be_bytes(),
+            });
+        }
+        _ => panic!("Unsuppoted length size {}", LENGTH_SIZE),
+    }
+    header[LENGTH_SIZE] = obj_id;
+
+    Ok((header, LENGTH_SIZE + 1))
+}
+
+/// Encodes `obj` into `buf` and returns the length of the value,
+/// even if it did not fit (`LTVError::BufferTooSmall`).
+pub(crate) fn encode_value<T: LTVItem<ED>, const ED: ByteOrder>(
+    obj: &T,
+    buf: &mut [u8],
+) -> LTVResult<usize> {
+    match obj.encode_ltv(buf) {
+        Ok(length) => Ok(length),
+        Err(LTVError::BufferTooSmall { required, .. }) => Ok(required),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes all of `bufs`, retrying on short writes and interrupts.
+fn write_all_vectored<W: io::Write>(w: &mut W, mut bufs: &mut [IoSlice<'_>]) -> io::Result<()> {
+    IoSlice::advance_slices(&mut bufs, 0);
+    while !bufs.is_empty() {
+        match w.write_vectored(bufs) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole LTV field",
+                ))
+            }
+            Ok(n) => IoSlice::advance_slices(&mut bufs, n),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Tracks the field order for canonical writers.
+#[derive(Default)]
+struct CanonicalOrder {
+    last_id: Option<u8>,
+}
+
+impl CanonicalOrder {
+    /// Fields must be written in ascending tag order, repeated tags (lists) must be consecutive.
+    fn check(&mut self, obj_id: u8) -> LTVResult<()> {
+        match self.last_id {
+            Some(previous) if obj_id < previous => Err(LTVError::UnorderedField {
+                field_id: obj_id,
+                previous,
+            }),
+            _ => {
+                self.last_id = Some(obj_id);
+                Ok(())
+            }
+        }
+    }
+}
 
 pub trait LTVContainer<const ED: ByteOrder, const LENGTH_SIZE: usize> {
-    fn write_ltv<'a, T>(&mut self, obj_id: u8, obj: &T) -> io::Result<usize>
+    /// Writes `obj` as a field. Empty values are not written.
+    fn write_ltv<'a, T>(&mut self, obj_id: u8, obj: &T) -> LTVResult<usize>
     where
         T: LTVItem<ED>;
+
+    /// Writes an already encoded value as a field, including when `data` is empty.
+    fn write_ltv_data(&mut self, obj_id: u8, data: &[u8]) -> LTVResult<usize>;
+
+    /// Whether the container only writes canonical output, see `LTVWriter::canonical`.
+    fn is_canonical(&self) -> bool {
+        false
+    }
+
+    /// Writes each item of `list` as an `obj_id` field.
+    ///
+    /// Canonical containers write unordered lists (`HashSet`) sorted by the encoded item.
+    fn write_ltv_list<M: LTVItemMany<ED>>(&mut self, obj_id: u8, list: &M) -> LTVResult<usize> {
+        let mut size = 0;
+        if M::UNORDERED && self.is_canonical() {
+            let mut items = list
+                .get_items()
+                .filter(|item| item.is_present())
+                .map(|item| item.try_to_ltv())
+                .collect::<LTVResult<Vec<_>>>()?;
+            items.sort();
+            for item in items {
+                size += self.write_ltv_data(obj_id, &item)?;
+            }
+        } else {
+            for item in list.get_items() {
+                size += self.write_ltv(obj_id, item)?;
+            }
+        }
+        Ok(size)
+    }
+
+    /// Writes each entry of `map` as an `obj_id` field holding the key in field `key_id`
+    /// and the value in field `value_id`.
+    ///
+    /// Entries are written in iteration order.
+    /// Canonical containers write unordered maps (`HashMap`) sorted by the encoded key.
+    fn write_ltv_map<M: LTVItemMap<ED>>(
+        &mut self,
+        obj_id: u8,
+        key_id: u8,
+        value_id: u8,
+        map: &M,
+    ) -> LTVResult<usize> {
+        let mut entries = Vec::new();
+        for (key, value) in map.get_entries() {
+            let key_data = key.try_to_ltv()?;
+            let mut entry = Vec::new();
+            // empty keys and values are written too, only absent ones (`None`) are left out
+            if key.is_present() {
+                LTVContainer::<ED, LENGTH_SIZE>::write_ltv_data(&mut entry, key_id, &key_data)?;
+            }
+            if value.is_present() {
+                let value_data = value.try_to_ltv()?;
+                LTVContainer::<ED, LENGTH_SIZE>::write_ltv_data(&mut entry, value_id, &value_data)?;
+            }
+            entries.push((key_data, entry));
+        }
+        if M::UNORDERED && self.is_canonical() {
+            entries.sort();
+        }
+
+        let mut size = 0;
+        for (_, entry) in entries {
+            size += self.write_ltv_data(obj_id, &entry)?;
+        }
+        Ok(size)
+    }
 }
 
 impl<W: io::Write, const ED: ByteOrder, const LENGTH_SIZE: usize> LTVContainer<ED, LENGTH_SIZE>
     for W
 {
-    fn write_ltv<'a, T: LTVItem<ED>>(&mut self, obj_id: u8, obj: &T) -> io::Result<usize> {
-        let data = obj.to_ltv();
+    fn write_ltv<'a, T: LTVItem<ED>>(&mut self, obj_id: u8, obj: &T) -> LTVResult<usize> {
+        if let Some(data) = obj.as_ltv_bytes() {
+            if data.is_empty() {
+                return Ok(0);
+            }
+            return <Self as LTVContainer<ED, LENGTH_SIZE>>::write_ltv_data(self, obj_id, data);
+        }
+
+        let data = obj.try_to_ltv()?;
         if data.len() == 0 {
             return Ok(0);
         }
+        <Self as LTVContainer<ED, LENGTH_SIZE>>::write_ltv_data(self, obj_id, &data)
+    }
 
-        let mut size: usize = 0;
-
-        match LENGTH_SIZE {
-            1 => size += self.write(&[(data.len() as u8) + 1, obj_id as u8])?,
-            2 => {
-                size += self.write(&match ED {
-                    ByteOrder::LE => (data.len() as u16).to_le_bytes(),
-                    ByteOrder::BE => (data.len() as u16).to_be_bytes(),
-                })?
-            }
-            _ => panic!("Unsuppoted length size {}", LENGTH_SIZE),
-        }
+    /// The header is built in a scratch buffer and written together with `data`
+    /// in a single vectored write, so `data` is never copied.
+    fn write_ltv_data(&mut self, obj_id: u8, data: &[u8]) -> LTVResult<usize> {
+        let (header, header_size) = encode_header::<ED, LENGTH_SIZE>(obj_id, data.len())?;
 
-        size += self.write(&data)?;
-        Ok(size)
+        let mut bufs = [IoSlice::new(&header[..header_size]), IoSlice::new(data)];
+        write_all_vectored(self, &mut bufs)?;
+        Ok(header_size + data.len())
     }
 }
 
@@ -41,13 +199,31 @@ pub struct LTVWriter<
     const LENGTH_SIZE: usize,
 > {
     writer: W,
+    canonical: Option<CanonicalOrder>,
 }
 
 impl<W: LTVContainer<ED, LENGTH_SIZE>, const ED: ByteOrder, const LENGTH_SIZE: usize>
     LTVWriter<W, ED, LENGTH_SIZE>
 {
     pub fn new(w: W) -> Self {
-        Self { writer: w }
+        Self {
+            writer: w,
+            canonical: None,
+        }
+    }
+
+    /// A writer that only produces canonical output, so equal values always encode to the same bytes.
+    ///
+    /// - fields must be written in ascending tag order, otherwise `LTVError::UnorderedField` is returned.
+    /// - absent values (`None`, `()`) are omitted.
+    /// - present but empty values (e.g. `Some(vec![])`) are written as a field with no data.
+    ///
+    /// Lengths always use `LENGTH_SIZE` bytes so every length already has a single encoding.
+    pub fn canonical(w: W) -> Self {
+        Self {
+            writer: w,
+            canonical: Some(CanonicalOrder::default()),
+        }
     }
 
     pub fn into_inner(self) -> W {
@@ -58,7 +234,121 @@ impl<W: LTVContainer<ED, LENGTH_SIZE>, const ED: ByteOrder, const LENGTH_SIZE: u
 impl<W: LTVContainer<ED, LENGTH_SIZE>, const ED: ByteOrder, const LENGTH_SIZE: usize>
     LTVContainer<ED, LENGTH_SIZE> for LTVWriter<W, ED, LENGTH_SIZE>
 {
-    fn write_ltv<'a, T: LTVItem<ED>>(&mut self, obj_id: u8, obj: &T) -> io::Result<usize> {
-        self.writer.write_ltv(obj_id, obj)
+    fn write_ltv<'a, T: LTVItem<ED>>(&mut self, obj_id: u8, obj: &T) -> LTVResult<usize> {
+        match &mut self.canonical {
+            Some(order) => {
+                if !obj.is_present() {
+                    return Ok(0);
+                }
+                order.check(obj_id)?;
+                match obj.as_ltv_bytes() {
+                    Some(data) => self.writer.write_ltv_data(obj_id, data),
+                    None => self.writer.write_ltv_data(obj_id, &obj.try_to_ltv()?),
+                }
+            }
+            None => self.writer.write_ltv(obj_id, obj),
+        }
+    }
+
+    fn write_ltv_data(&mut self, obj_id: u8, data: &[u8]) -> LTVResult<usize> {
+        if let Some(order) = &mut self.canonical {
+            order.check(obj_id)?;
+        }
+        self.writer.write_ltv_data(obj_id, data)
+    }
+
+    fn is_canonical(&self) -> bool {
+        self.canonical.is_some()
+    }
+}
+
+/// Writes LTV fields into a fixed buffer without allocating.
+///
+/// When the buffer runs out the writer keeps counting, so `finish` can report
+/// the total size needed in `LTVError::BufferTooSmall`.
+pub struct LTVSliceWriter<'a, const ED: ByteOrder, const LENGTH_SIZE: usize> {
+    buf: &'a mut [u8],
+    position: usize,
+    canonical: Option<CanonicalOrder>,
+}
+
+impl<'a, const ED: ByteOrder, const LENGTH_SIZE: usize> LTVSliceWriter<'a, ED, LENGTH_SIZE> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            buf,
+            position: 0,
+            canonical: None,
+        }
+    }
+
+    /// Canonical output, see `LTVWriter::canonical`.
+    pub fn canonical(buf: &'a mut [u8]) -> Self {
+        Self {
+            buf,
+            position: 0,
+            canonical: Some(CanonicalOrder::default()),
+        }
+    }
+
+    /// Returns the number of bytes written,
+    /// or `LTVError::BufferTooSmall` if everything written did not fit.
+    pub fn finish(self) -> LTVResult<usize> {
+        if self.position > self.buf.len() {
+            return Err(LTVError::BufferTooSmall {
+                required: self.position,
+                available: self.buf.len(),
+            });
+        }
+        Ok(self.position)
+    }
+}
+
+impl<'a, const ED: ByteOrder, const LENGTH_SIZE: usize> LTVContainer<ED, LENGTH_SIZE>
+    for LTVSliceWriter<'a, ED, LENGTH_SIZE>
+{
+    fn write_ltv<'b, T: LTVItem<ED>>(&mut self, obj_id: u8, obj: &T) -> LTVResult<usize> {
+        if let Some(order) = &mut self.canonical {
+            if !obj.is_present() {
+                return Ok(0);
+            }
+            order.check(obj_id)?;
+        }
+
+        let header_size = LENGTH_SIZE + 1;
+        let start = self.position;
+        let value_start = start + header_size;
+
+        let data_length = encode_value(obj, self.buf.get_mut(value_start..).unwrap_or_default())?;
+        if data_length == 0 && self.canonical.is_none() {
+            return Ok(0);
+        }
+        let (header, _) = encode_header::<ED, LENGTH_SIZE>(obj_id, data_length)?;
+
+        let size = header_size + data_length;
+        if let Some(out) = self.buf.get_mut(start..start + size) {
+            out[..header_size].copy_from_slice(&header[..header_size]);
+        }
+        self.position += size;
+        Ok(size)
+    }
+
+    fn write_ltv_data(&mut self, obj_id: u8, data: &[u8]) -> LTVResult<usize> {
+        if let Some(order) = &mut self.canonical {
+            order.check(obj_id)?;
+        }
+
+        let (header, header_size) = encode_header::<ED, LENGTH_SIZE>(obj_id, data.len())?;
+        let start = self.position;
+        let size = header_size + data.len();
+        if let Some(out) = self.buf.get_mut(start..start + size) {
+            out[..header_size].copy_from_slice(&header[..header_size]);
+            out[header_size..].copy_from_slice(data);
+        }
+        self.position += size;
+        Ok(size)
+    }
+
+    fn is_canonical(&self) -> bool {
+        self.canonical.is_some()
     }
 }
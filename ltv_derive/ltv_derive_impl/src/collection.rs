@@ -10,27 +10,34 @@ struct LtvCollectionInfo {
     pub inner_data: Field,
 }
 
-pub fn impl_ltv_collection(input: DeriveInput) -> proc_macro2::TokenStream {
-    let attrs = LTVObjectAttrabutes::parse(&input);
+pub fn impl_ltv_collection(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = LTVObjectAttrabutes::parse(&input)?;
     let enum_ident = input.ident;
 
     let variants: Vec<LtvCollectionInfo> = match input.data {
         Data::Enum(DataEnum { variants, .. }) => variants,
-        _ => panic!("this derive macro only works on enums"),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &enum_ident,
+                "this derive macro only works on enums",
+            ))
+        }
     }
     .into_iter()
     .map(|v| {
-        let inner_data = v
-            .fields
-            .into_iter()
-            .next()
-            .expect("Requires enums with LTVItems as fields.  `MyEnum::Object1(MyLtvItem)");
-        LtvCollectionInfo {
+        let span_variant = v.ident.clone();
+        let inner_data = v.fields.into_iter().next().ok_or_else(|| {
+            syn::Error::new_spanned(
+                span_variant,
+                "Requires enums with LTVItems as fields.  `MyEnum::Object1(MyLtvItem)",
+            )
+        })?;
+        Ok(LtvCollectionInfo {
             enum_field: v.ident,
             inner_data,
-        }
+        })
     })
-    .collect();
+    .collect::<syn::Result<_>>()?;
 
     let byte_order = match attrs.byte_order {
         ByteOrderOption::BE => quote! { {::ltv::ByteOrder::BE} },
@@ -81,16 +88,35 @@ pub fn impl_ltv_collection(input: DeriveInput) -> proc_macro2::TokenStream {
             let branch_name = &info.enum_field;
             let inner_ltv = &info.inner_data;
             quote! {
-                Self::#branch_name(v) => <#inner_ltv as LTVItem<#byte_order>>::to_ltv(v)
+                Self::#branch_name(v) => <#inner_ltv as LTVItem<#byte_order>>::try_to_ltv(v)
+            }
+        });
+
+        let encode_branches = variants.iter().map(|info| {
+            let branch_name = &info.enum_field;
+            let inner_ltv = &info.inner_data;
+            quote! {
+                Self::#branch_name(v) => <#inner_ltv as LTVItem<#byte_order>>::encode_ltv(v, buf)
             }
         });
 
         quote! {
             fn to_ltv(&self) -> Vec<u8>{
+                <Self as LTVItem<#byte_order>>::try_to_ltv(self)
+                    .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+            }
+
+            fn try_to_ltv(&self) -> ::ltv::LTVResult<Vec<u8>>{
                 match self {
                     #(#object_match_branches),*
                 }
             }
+
+            fn encode_ltv(&self, buf: &mut [u8]) -> ::ltv::LTVResult<usize>{
+                match self {
+                    #(#encode_branches),*
+                }
+            }
         }
     };
 
@@ -99,7 +125,7 @@ pub fn impl_ltv_collection(input: DeriveInput) -> proc_macro2::TokenStream {
             let branch_name = &info.enum_field;
             let inner_ltv = &info.inner_data;
             quote! {
-                Self::#branch_name(v) => <#inner_ltv as LTVObjectConvertable<#byte_order, #len_size>>::to_ltv_object(v)
+                Self::#branch_name(v) => <#inner_ltv as LTVObjectConvertable<#byte_order, #len_size>>::try_to_ltv_object(v)
             }
         });
 
@@ -110,7 +136,63 @@ pub fn impl_ltv_collection(input: DeriveInput) -> proc_macro2::TokenStream {
         }
     };
 
+    let encode_ltv_object_branches = {
+        let object_match_branches = variants.iter().map(|info| {
+            let branch_name = &info.enum_field;
+            let inner_ltv = &info.inner_data;
+            quote! {
+                Self::#branch_name(v) => <#inner_ltv as LTVObjectConvertable<#byte_order, #len_size>>::encode_ltv_object(v, buf)
+            }
+        });
+
+        quote! {
+            match self {
+                #(#object_match_branches),*
+            }
+        }
+    };
+
+    // object ids are only known once the inner types are compiled
+    let unique_ids = {
+        let ids = variants.iter().map(|info| {
+            let inner_ltv = &info.inner_data;
+            quote! { <#inner_ltv as ::ltv::LTVObject<#len_size>>::OBJECT_ID }
+        });
+        let duplicate_msg = format!("{} has variants with the same OBJECT_ID", enum_ident);
+        let reserved_msg = format!("{} has a variant with a reserved OBJECT_ID", enum_ident);
+        let reserved = attrs.reserved.iter().map(|r| {
+            // skip the bounds that are always true to not trip `unused_comparisons`
+            let below = (*r.start() > 0).then(|| {
+                let start = r.start();
+                quote! { ids[i] < #start }
+            });
+            let above = (*r.end() < u8::MAX).then(|| {
+                let end = r.end();
+                quote! { ids[i] > #end }
+            });
+            let outside = below.into_iter().chain(above);
+            quote! { assert!(false #(|| #outside)*, #reserved_msg); }
+        });
+        quote! {
+            const _: () = {
+                let ids = [#(#ids),*];
+                let mut i = 0;
+                while i < ids.len() {
+                    #(#reserved)*
+                    let mut j = i + 1;
+                    while j < ids.len() {
+                        assert!(ids[i] != ids[j], #duplicate_msg);
+                        j += 1;
+                    }
+                    i += 1;
+                }
+            };
+        }
+    };
+
     let e = quote! {
+        #unique_ids
+
         #[automatically_derived]
         #byte_order_impl for #enum_ident {
             #from_ltv_fn
@@ -123,9 +205,13 @@ pub fn impl_ltv_collection(input: DeriveInput) -> proc_macro2::TokenStream {
                 <Self as LTVItem<#byte_order>>::from_ltv(obj_id, data)
             }
 
-            fn to_ltv_object(&self) -> Vec<u8> {
+            fn try_to_ltv_object(&self) -> LTVResult<Vec<u8>> {
                 #to_ltv_object_branches
             }
+
+            fn encode_ltv_object(&self, buf: &mut [u8]) -> LTVResult<usize> {
+                #encode_ltv_object_branches
+            }
         }
     };
     /*
@@ -136,5 +222,5 @@ pub fn impl_ltv_collection(input: DeriveInput) -> proc_macro2::TokenStream {
     )
     .ok();
     */
-    e
+    Ok(e)
 }
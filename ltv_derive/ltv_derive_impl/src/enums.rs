@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+
+use super::object::{ByteOrderOption, LTVObjectAttrabutes, LtvAttributes, LtvFieldAttributes};
+
+use ::quote::quote;
+use syn::{DataEnum, DeriveInput, Fields, Ident};
+
+const INTS: [&str; 8] = ["u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64"];
+
+/// The integer type from `#[repr(..)]`, if any.
+fn repr_type(input: &DeriveInput) -> Option<Ident> {
+    input
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("repr"))
+        .flat_map(|a| {
+            a.parse_args_with(syn::punctuated::Punctuated::<Ident, syn::Token![,]>::parse_terminated)
+                .map(|idents| idents.into_iter().collect::<Vec<_>>())
+                .unwrap_or_default()
+        })
+        .find(|i| INTS.iter().any(|int| i == int))
+}
+
+/// Unit-only enums, written as their discriminant.
+///
+/// The integer type comes from `#[repr(..)]`, or else the `#[ltv(other)]` variant, or is `u8`.
+/// Unknown values decode into the `#[ltv(other)] Unknown(T)` variant if there is one
+/// and fail with `LTVError::UnexpectedValue` otherwise.
+fn impl_ltv_c_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = LTVObjectAttrabutes::parse(input)?;
+    let enum_ident = &input.ident;
+    let enum_name = enum_ident.to_string();
+
+    let mut other = None;
+    let mut variants = Vec::new();
+    // implicit discriminants count up from the last explicit one
+    let mut base = None;
+    let mut offset = 0usize;
+    for v in &data.variants {
+        let full_name = format!("{}::{}", enum_name, v.ident);
+        if LtvAttributes::parse(&v.attrs)?.other {
+            let field = match &v.fields {
+                Fields::Unnamed(f) if f.unnamed.len() == 1 => f.unnamed[0].ty.clone(),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        v,
+                        format!("{} must hold the unknown value, e.g. `Unknown(u16)`", full_name),
+                    ))
+                }
+            };
+            if other.replace((v.ident.clone(), field)).is_some() {
+                return Err(syn::Error::new_spanned(
+                    v,
+                    format!("{} can only have one #[ltv(other)] variant", enum_name),
+                ));
+            }
+            continue;
+        }
+        if !matches!(v.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                v,
+                format!("{} must be a unit variant or marked #[ltv(other)]", full_name),
+            ));
+        }
+        if let Some((_, expr)) = &v.discriminant {
+            base = Some(expr.clone());
+            offset = 0;
+        }
+        variants.push((v.ident.clone(), base.clone(), offset));
+        offset += 1;
+    }
+
+    // the unknown value is written as is, so it must be the same integer
+    if let Some((ident, ty)) = &other {
+        let full_name = format!("{}::{}", enum_name, ident);
+        let ty_ident = match ty {
+            syn::Type::Path(p) => p.path.get_ident(),
+            _ => None,
+        };
+        match (repr_type(input), ty_ident) {
+            (Some(repr), Some(ty_ident)) if *ty_ident == repr => {}
+            (Some(repr), _) => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!("{} must hold the `#[repr({})]` type, e.g. `Unknown({})`", full_name, repr, repr),
+                ))
+            }
+            (None, Some(ty_ident)) if INTS.iter().any(|int| ty_ident == int) => {}
+            (None, _) => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!("{} must hold an integer type, e.g. `Unknown(u16)`", full_name),
+                ))
+            }
+        }
+    }
+
+    let repr = match (repr_type(input), &other) {
+        (Some(repr), _) => quote! { #repr },
+        (None, Some((_, ty))) => quote! { #ty },
+        (None, None) => quote! { u8 },
+    };
+    // typed as `repr` like the real discriminant, so e.g. `0x8000_0000` is not an `i32` literal
+    let variants: Vec<_> = variants
+        .into_iter()
+        .map(|(ident, base, offset)| {
+            let literal = proc_macro2::Literal::usize_unsuffixed(offset);
+            let discriminant = match (base, offset) {
+                (Some(base), 0) => quote! { ::core::convert::identity::<#repr>(#base) },
+                (Some(base), _) => quote! { ::core::convert::identity::<#repr>(#base) + #literal },
+                (None, _) => quote! { ::core::convert::identity::<#repr>(#literal) },
+            };
+            (ident, discriminant)
+        })
+        .collect();
+
+    let byte_order = match attrs.byte_order {
+        ByteOrderOption::BE => quote! { {::ltv::ByteOrder::BE} },
+        ByteOrderOption::LE => quote! { {::ltv::ByteOrder::LE} },
+        ByteOrderOption::None => quote! { ED },
+    };
+
+    let byte_order_impl = match attrs.byte_order {
+        ByteOrderOption::BE => quote! { impl },
+        ByteOrderOption::LE => quote! { impl },
+        ByteOrderOption::None => quote! {impl<const ED: ::ltv::ByteOrder> },
+    };
+
+    let decode_branches = variants.iter().map(|(ident, discriminant)| {
+        quote! {
+            if value == #discriminant {
+                return Ok(Self::#ident);
+            }
+        }
+    });
+    let unknown = match &other {
+        Some((ident, _)) => quote! { Ok(Self::#ident(value)) },
+        None => quote! {
+            Err(::ltv::LTVError::UnexpectedValue(
+                field_id,
+                format!("unknown {} value {}", #enum_name, value),
+            ))
+        },
+    };
+
+    let encode_branches = variants.iter().map(|(ident, discriminant)| {
+        quote! { Self::#ident => #discriminant, }
+    });
+    let encode_other = other.as_ref().map(|(ident, _)| {
+        quote! { Self::#ident(value) => *value, }
+    });
+    let value = quote! {
+        match self {
+            #(#encode_branches)*
+            #encode_other
+        }
+    };
+
+    let len_size = attrs.length_size.unwrap_or(1) as usize;
+    let obj_impl = attrs.object_id.map(|obj_id| {
+        quote! {
+            #[automatically_derived]
+            impl ::ltv::LTVObject<#len_size> for #enum_ident {
+                const OBJECT_ID: u8 = #obj_id;
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        #byte_order_impl ::ltv::LTVItem<#byte_order> for #enum_ident {
+            const FIXED_SIZE: Option<usize> = <#repr as ::ltv::LTVItem<#byte_order>>::FIXED_SIZE;
+
+            fn from_ltv(field_id: u8, data: &[u8]) -> ::ltv::LTVResult<Self> {
+                let value = <#repr as ::ltv::LTVItem<#byte_order>>::from_ltv(field_id, data)?;
+                #(#decode_branches)*
+                #unknown
+            }
+
+            fn to_ltv(&self) -> Vec<u8> {
+                <#repr as ::ltv::LTVItem<#byte_order>>::to_ltv(&#value)
+            }
+
+            fn encode_ltv(&self, buf: &mut [u8]) -> ::ltv::LTVResult<usize> {
+                <#repr as ::ltv::LTVItem<#byte_order>>::encode_ltv(&#value, buf)
+            }
+        }
+
+        #obj_impl
+    })
+}
+
+/// Enums whose variants are tagged with `#[ltv_field(id)]`, written like a struct
+/// holding only the field of the current variant.
+///
+/// Decoding fails with `LTVError::OneOf` unless exactly one of the tags is present.
+/// Unit variants are written as an empty field.
+fn impl_ltv_one_of(input: &DeriveInput, data: &DataEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = LTVObjectAttrabutes::parse(input)?;
+    let enum_ident = &input.ident;
+    let enum_name = enum_ident.to_string();
+
+    let mut used_ids = HashMap::new();
+    let variants: Vec<(Ident, u8, Option<syn::Type>)> = data
+        .variants
+        .iter()
+        .map(|v| {
+            let full_name = format!("{}::{}", enum_ident, v.ident);
+            let attr = v
+                .attrs
+                .iter()
+                .find(|a| a.path.is_ident("ltv_field"))
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(&v.ident, format!("{} does not have ltv_field", full_name))
+                })?;
+            let field_args = LtvFieldAttributes::parse(attr)?;
+            attrs.check_field_id(&mut used_ids, field_args.ltv_id, &full_name, attr)?;
+            if !field_args.is_id_only() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!("{} can only have an id in ltv_field", full_name),
+                ));
+            }
+            let ty = match &v.fields {
+                Fields::Unit => None,
+                Fields::Unnamed(f) if f.unnamed.len() == 1 => Some(f.unnamed[0].ty.clone()),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &v.fields,
+                        format!("{} must be a unit variant or hold a single value", full_name),
+                    ))
+                }
+            };
+            Ok((v.ident.clone(), field_args.ltv_id, ty))
+        })
+        .collect::<syn::Result<_>>()?;
+
+    let byte_order = match attrs.byte_order {
+        ByteOrderOption::BE => quote! { {::ltv::ByteOrder::BE} },
+        ByteOrderOption::LE => quote! { {::ltv::ByteOrder::LE} },
+        ByteOrderOption::None => quote! { ED },
+    };
+
+    let byte_order_impl = match attrs.byte_order {
+        ByteOrderOption::BE => quote! { impl },
+        ByteOrderOption::LE => quote! { impl },
+        ByteOrderOption::None => quote! {impl<const ED: ::ltv::ByteOrder> },
+    };
+
+    let field_length_size = attrs.field_length_size.unwrap_or(1) as usize;
+    let writer_new = attrs.writer_constructor();
+
+    let ids = variants.iter().map(|(_, ltv_id, _)| ltv_id);
+    let decode_branches = variants.iter().map(|(ident, ltv_id, ty)| {
+        let branch_err_name = format!("{}::{}", enum_ident, ident);
+        match ty {
+            Some(ty) => quote! {
+                #ltv_id => Ok(Self::#ident(
+                    <#ty as ::ltv::LTVItem<#byte_order>>::from_ltv(#ltv_id, variant_data).map_err(|e| {
+                        ::ltv::LTVError::InnerParseError(e.into(), String::from(#branch_err_name))
+                    })?,
+                )),
+            },
+            None => quote! { #ltv_id if variant_data.is_empty() => Ok(Self::#ident), },
+        }
+    });
+
+    let encode_branches = variants.iter().map(|(ident, ltv_id, ty)| match ty {
+        Some(ty) => quote! {
+            Self::#ident(value) => {
+                let data = <#ty as ::ltv::LTVItem<#byte_order>>::try_to_ltv(value)?;
+                ::ltv::LTVContainer::<#byte_order, #field_length_size>::write_ltv_data(&mut buffer, #ltv_id, &data)?;
+            }
+        },
+        None => quote! {
+            Self::#ident => {
+                ::ltv::LTVContainer::<#byte_order, #field_length_size>::write_ltv_data(&mut buffer, #ltv_id, &[])?;
+            }
+        },
+    });
+    let encode = quote! {
+        match self {
+            #(#encode_branches)*
+        }
+    };
+
+    let len_size = attrs.length_size.unwrap_or(1) as usize;
+    let obj_impl = attrs.object_id.map(|obj_id| {
+        quote! {
+            #[automatically_derived]
+            impl ::ltv::LTVObject<#len_size> for #enum_ident {
+                const OBJECT_ID: u8 = #obj_id;
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        #byte_order_impl ::ltv::LTVItem<#byte_order> for #enum_ident {
+            fn from_ltv(field_id: u8, data: &[u8]) -> ::ltv::LTVResult<Self> {
+                let reader = ::ltv::LTVReader::<#byte_order, #field_length_size>::new(data);
+                let (variant_id, variant_data) = reader.get_one_of(field_id, &[#(#ids),*])?;
+                match variant_id {
+                    #(#decode_branches)*
+                    // unit variants with a value
+                    _ => Err(::ltv::LTVError::UnexpectedValue(
+                        variant_id,
+                        format!("{} variant should not have a value", #enum_name),
+                    )),
+                }
+            }
+
+            fn to_ltv(&self) -> Vec<u8> {
+                <Self as ::ltv::LTVItem<#byte_order>>::try_to_ltv(self)
+                    .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+            }
+
+            fn try_to_ltv(&self) -> ::ltv::LTVResult<Vec<u8>> {
+                let mut buffer = ::ltv::LTVWriter::<_, #byte_order, #field_length_size>::#writer_new(Vec::new());
+                #encode
+                Ok(buffer.into_inner())
+            }
+
+            fn encode_ltv(&self, buf: &mut [u8]) -> ::ltv::LTVResult<usize> {
+                let mut buffer = ::ltv::LTVSliceWriter::<#byte_order, #field_length_size>::#writer_new(buf);
+                #encode
+                buffer.finish()
+            }
+        }
+
+        #obj_impl
+    })
+}
+
+pub fn impl_ltv_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let is_one_of = data
+        .variants
+        .iter()
+        .any(|v| v.attrs.iter().any(|a| a.path.is_ident("ltv_field")));
+    if is_one_of {
+        impl_ltv_one_of(input, data)
+    } else {
+        impl_ltv_c_enum(input, data)
+    }
+}
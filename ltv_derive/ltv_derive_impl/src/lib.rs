@@ -1,17 +1,22 @@
 #![feature(adt_const_params)]
 mod collection;
+mod enums;
 mod object;
 
 use syn::{parse_macro_input, DeriveInput};
 
-#[proc_macro_derive(Ltv, attributes(ltv_field, ltv_field_list, object))]
+#[proc_macro_derive(Ltv, attributes(ltv, ltv_field, ltv_field_list, ltv_field_map, object))]
 pub fn derive_ltv(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    object::impl_ltv(input).into()
+    object::impl_ltv(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
 #[proc_macro_derive(LtvCollection, attributes(object))]
 pub fn derive_ltv_set(input_tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input_tokens as DeriveInput);
-    collection::impl_ltv_collection(input).into()
+    collection::impl_ltv_collection(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
@@ -1,13 +1,230 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use ::quote::quote;
+use ::quote::{format_ident, quote};
 use proc_macro2;
-use syn::{Data, DataStruct, DeriveInput, Fields, Ident, LitBool, LitInt, Token, parenthesized, parse::{ParseStream, Parser}};
+use syn::{Data, DataStruct, DeriveInput, Fields, Ident, Lit, LitBool, LitInt, LitStr, Token, parenthesized, parse::{ParseStream, Parser}};
 struct LtvFieldInfo {
     ltv_id: u8,
     ident: Option<syn::Ident>,
     ty: syn::Type,
     is_list: bool,
+    /// Adapter type the value is encoded as, e.g. `NarrowInt` for `width = 3`.
+    wire_ty: Option<proc_macro2::TokenStream>,
+    /// Key and value tags of a `ltv_field_map` entry.
+    map_ids: Option<(u8, u8)>,
+    /// Value used when the field is missing, from `#[ltv(default)]`.
+    default: Option<proc_macro2::TokenStream>,
+    /// Condition on `self` to not write the field, from `#[ltv(skip_if = "...")]`.
+    skip_if: Option<proc_macro2::TokenStream>,
+    /// Function of `&Self` that gives the value, from `#[ltv(computed = "...")]`.
+    computed: Option<syn::ExprPath>,
+}
+
+/// Arguments of `#[ltv(...)]` on a field or variant.
+#[derive(Default)]
+pub(crate) struct LtvAttributes {
+    /// `other`: catch-all variant of a C-like enum.
+    pub(crate) other: bool,
+    /// `default` or `default = "path::to::fn"`.
+    pub(crate) default: Option<Option<syn::ExprPath>>,
+    /// `skip_if = "path::to::fn"`, called with a reference to the field.
+    pub(crate) skip_if: Option<syn::ExprPath>,
+    /// `skip_if_default`, also implies `default`.
+    pub(crate) skip_if_default: bool,
+    /// `skip`: not on the wire, `Default` when decoding.
+    pub(crate) skip: bool,
+    /// `computed = "path::to::fn"`, called with `&Self` when encoding and checked when decoding.
+    pub(crate) computed: Option<syn::ExprPath>,
+}
+
+impl LtvAttributes {
+    pub(crate) fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut args = LtvAttributes::default();
+        for attr in attrs.iter().filter(|a| a.path.is_ident("ltv")) {
+            attr.parse_args_with(|input: ParseStream<'_>| {
+                while !input.is_empty() {
+                    let ident: Ident = input.parse()?;
+                    match ident.to_string().as_str() {
+                        "other" => args.other = true,
+                        "default" => {
+                            args.default = Some(if input.peek(Token![=]) {
+                                let _: Token![=] = input.parse()?;
+                                Some(input.parse::<LitStr>()?.parse()?)
+                            } else {
+                                None
+                            });
+                        }
+                        "skip_if" => {
+                            let _: Token![=] = input.parse()?;
+                            args.skip_if = Some(input.parse::<LitStr>()?.parse()?);
+                        }
+                        "skip_if_default" => args.skip_if_default = true,
+                        "skip" => args.skip = true,
+                        "computed" => {
+                            let _: Token![=] = input.parse()?;
+                            args.computed = Some(input.parse::<LitStr>()?.parse()?);
+                        }
+                        _ => {
+                            return Err(syn::parse::Error::new(
+                                ident.span(),
+                                format!("unknown argument `{}`", ident),
+                            ))
+                        }
+                    }
+                    if input.is_empty() {
+                        break;
+                    }
+                    let _: Token![,] = input.parse()?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(args)
+    }
+}
+
+/// Arguments of `#[ltv_field(id, ...)]`, `#[ltv_field_list(id, ...)]` and `#[ltv_field_map(id, ...)]`.
+#[derive(Default)]
+pub(crate) struct LtvFieldAttributes {
+    pub(crate) ltv_id: u8,
+    width: Option<usize>,
+    compact: Option<bool>,
+    varint: bool,
+    key: Option<u8>,
+    value: Option<u8>,
+    unit: Option<u64>,
+    bits: Option<usize>,
+    epoch: Option<i64>,
+    flags: Option<Ident>,
+    /// Decimal places of a `scale = 0.01` field.
+    decimals: Option<u32>,
+    repr: Option<syn::Type>,
+}
+
+impl LtvFieldAttributes {
+    pub(crate) fn parse(attr: &syn::Attribute) -> syn::parse::Result<Self> {
+        attr.parse_args_with(|input: ParseStream<'_>| {
+            let mut args = LtvFieldAttributes {
+                ltv_id: input.parse::<LitInt>()?.base10_parse()?,
+                ..Default::default()
+            };
+
+            while !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+                if input.is_empty() {
+                    break;
+                }
+
+                let ident: Ident = input.parse()?;
+                let _eq_token: Token![=] = input.parse()?;
+                match ident.to_string().as_str() {
+                    "width" => {
+                        args.width = Some(input.parse::<LitInt>()?.base10_parse()?);
+                    }
+                    "compact" => {
+                        args.compact = Some(input.parse::<LitBool>()?.value());
+                    }
+                    "varint" => {
+                        args.varint = input.parse::<LitBool>()?.value();
+                    }
+                    "key" => {
+                        args.key = Some(input.parse::<LitInt>()?.base10_parse()?);
+                    }
+                    "value" => {
+                        args.value = Some(input.parse::<LitInt>()?.base10_parse()?);
+                    }
+                    "unit" => {
+                        let unit = input.parse::<LitStr>()?;
+                        args.unit = Some(match unit.value().as_str() {
+                            "s" => 1,
+                            "ms" => 1_000,
+                            "us" => 1_000_000,
+                            "ns" => 1_000_000_000,
+                            _ => {
+                                return Err(syn::parse::Error::new(
+                                    unit.span(),
+                                    "unit must be \"s\", \"ms\", \"us\" or \"ns\"",
+                                ))
+                            }
+                        });
+                    }
+                    "bits" => {
+                        let bits = input.parse::<LitInt>()?;
+                        args.bits = Some(match bits.base10_parse()? {
+                            32 => 4,
+                            64 => 8,
+                            _ => {
+                                return Err(syn::parse::Error::new(
+                                    bits.span(),
+                                    "bits must be 32 or 64",
+                                ))
+                            }
+                        });
+                    }
+                    "epoch" => {
+                        args.epoch = Some(input.parse::<LitInt>()?.base10_parse()?);
+                    }
+                    "flags" => {
+                        let policy = input.parse::<LitStr>()?;
+                        args.flags = Some(match policy.value().as_str() {
+                            "retain" => format_ident!("Retain"),
+                            "truncate" => format_ident!("Truncate"),
+                            "reject" => format_ident!("Reject"),
+                            _ => {
+                                return Err(syn::parse::Error::new(
+                                    policy.span(),
+                                    "flags must be \"retain\", \"truncate\" or \"reject\"",
+                                ))
+                            }
+                        });
+                    }
+                    "scale" => {
+                        let scale = input.parse::<Lit>()?;
+                        let value: f64 = match &scale {
+                            Lit::Float(f) => f.base10_parse()?,
+                            Lit::Int(i) => i.base10_parse()?,
+                            _ => return Err(syn::parse::Error::new(scale.span(), "scale must be a number")),
+                        };
+                        args.decimals = Some(
+                            (0..=18)
+                                .find(|d| (10f64.powi(-d) - value).abs() <= value * 1e-9)
+                                .ok_or_else(|| {
+                                    syn::parse::Error::new(
+                                        scale.span(),
+                                        "scale must be a power of ten from 1 to 1e-18",
+                                    )
+                                })? as u32,
+                        );
+                    }
+                    "repr" => {
+                        args.repr = Some(input.parse()?);
+                    }
+                    _ => {
+                        return Err(syn::parse::Error::new(
+                            ident.span(),
+                            format!("unknown argument `{}`", ident),
+                        ))
+                    }
+                }
+            }
+            Ok(args)
+        })
+    }
+
+    /// `true` if nothing but the id is given.
+    pub(crate) fn is_id_only(&self) -> bool {
+        self.width.is_none()
+            && self.compact.is_none()
+            && !self.varint
+            && self.key.is_none()
+            && self.value.is_none()
+            && self.unit.is_none()
+            && self.bits.is_none()
+            && self.epoch.is_none()
+            && self.flags.is_none()
+            && self.decimals.is_none()
+            && self.repr.is_none()
+    }
 }
 
 #[derive(Debug)]
@@ -29,10 +246,14 @@ pub struct LTVObjectAttrabutes {
     pub field_length_size: Option<u8>,
     pub byte_order: ByteOrderOption,
     pub many: bool,
+    pub canonical: bool,
+    pub compact: bool,
+    /// Retired field ids from `reserved(5, 9..=12)`.
+    pub reserved: Vec<std::ops::RangeInclusive<u8>>,
 }
 
 impl LTVObjectAttrabutes {
-    pub fn parse(input: &DeriveInput) -> Self {
+    pub fn parse(input: &DeriveInput) -> syn::Result<Self> {
         if let Some(a) = input
             .attrs
             .iter()
@@ -40,7 +261,7 @@ impl LTVObjectAttrabutes {
             .next()
         {
             let tokens = a.tokens.clone();
-            let o = (|input_bracketed: ParseStream<'_>| -> syn::parse::Result<Self> {
+            (|input_bracketed: ParseStream<'_>| -> syn::parse::Result<Self> {
                 let input;
                 parenthesized!(input in input_bracketed);
 
@@ -53,7 +274,9 @@ impl LTVObjectAttrabutes {
                     }
 
                     let ident: Ident = input.parse()?;
-                    let _eq_token: Token![=] = input.parse()?;
+                    if ident != "reserved" {
+                        let _eq_token: Token![=] = input.parse()?;
+                    }
 
                     if !seen_arguments.insert(ident.clone()) {
                         return Err(syn::parse::Error::new(
@@ -92,7 +315,8 @@ impl LTVObjectAttrabutes {
                                 })?);
                         }
                         "byte_order" => {
-                            match input.parse::<Ident>()?.to_string().to_uppercase().as_str() {
+                            let byte_order = input.parse::<Ident>()?;
+                            match byte_order.to_string().to_uppercase().as_str() {
                                 "BE" => {
                                     ltv_args.byte_order = ByteOrderOption::BE;
                                 }
@@ -101,7 +325,7 @@ impl LTVObjectAttrabutes {
                                 }
                                 _ => {
                                     return Err(syn::parse::Error::new(
-                                        ident.span(),
+                                        byte_order.span(),
                                         "byte_order must be BE or LE",
                                     ))
                                 }
@@ -110,7 +334,47 @@ impl LTVObjectAttrabutes {
                         "many" => {
                             ltv_args.many = input.parse::<LitBool>()?.value();
                         }
-                        _ => panic!("Invalid argument {}", &ident_str),
+                        "canonical" => {
+                            ltv_args.canonical = input.parse::<LitBool>()?.value();
+                        }
+                        "compact" => {
+                            ltv_args.compact = input.parse::<LitBool>()?.value();
+                        }
+                        "reserved" => {
+                            let ids;
+                            parenthesized!(ids in input);
+                            while !ids.is_empty() {
+                                let start_lit = ids.parse::<LitInt>()?;
+                                let start: u8 = start_lit.base10_parse()?;
+                                let end: u8 = if ids.peek(Token![..=]) {
+                                    let dots: Token![..=] = ids.parse()?;
+                                    let end_lit = ids.parse::<LitInt>()?;
+                                    let end = end_lit.base10_parse()?;
+                                    if start > end {
+                                        return Err(syn::Error::new_spanned(
+                                            quote! { #start_lit #dots #end_lit },
+                                            format!("reserved range `{}..={}` is empty, the start must not be after the end", start, end),
+                                        ));
+                                    }
+                                    end
+                                } else if ids.peek(Token![..]) {
+                                    return Err(ids.error("reserved ranges must be inclusive, e.g. `9..=12`"));
+                                } else {
+                                    start
+                                };
+                                ltv_args.reserved.push(start..=end);
+                                if ids.is_empty() {
+                                    break;
+                                }
+                                let _: Token![,] = ids.parse()?;
+                            }
+                        }
+                        _ => {
+                            return Err(syn::parse::Error::new(
+                                ident.span(),
+                                format!("unknown argument `{}`", ident_str),
+                            ))
+                        }
                     }
 
                     if input.is_empty() {
@@ -121,63 +385,260 @@ impl LTVObjectAttrabutes {
                 Ok(ltv_args)
             })
             .parse2(tokens)
-            .unwrap();
-            o
         } else {
-            Self::default()
+            Ok(Self::default())
+        }
+    }
+
+    /// Fails at `attr` if `ltv_id` is reserved or already in `used`, which maps ids to field names.
+    pub fn check_field_id(
+        &self,
+        used: &mut HashMap<u8, String>,
+        ltv_id: u8,
+        name: &str,
+        attr: &syn::Attribute,
+    ) -> syn::Result<()> {
+        if self.reserved.iter().any(|r| r.contains(&ltv_id)) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("{} uses reserved field id {}", name, ltv_id),
+            ));
+        }
+        if let Some(other) = used.insert(ltv_id, name.to_string()) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("{} uses field id {} which is already used by {}", name, ltv_id, other),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Constructor used for `LTVWriter`/`LTVSliceWriter` in generated encoders.
+    pub fn writer_constructor(&self) -> Ident {
+        if self.canonical {
+            format_ident!("canonical")
+        } else {
+            format_ident!("new")
         }
     }
 }
 
+fn is_primitive_int(ty: &syn::Type) -> bool {
+    const INTS: [&str; 10] = [
+        "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "u128", "i128",
+    ];
+    match ty {
+        syn::Type::Path(p) => INTS.iter().any(|i| p.path.is_ident(i)),
+        _ => false,
+    }
+}
+
+/// `Option<T>` (also as `std::option::Option<T>`), which decodes a missing field as `None`.
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(p) => p.path.segments.last().is_some_and(|s| s.ident == "Option"),
+        _ => false,
+    }
+}
+
 fn impl_ltv_named(
     input: &DeriveInput,
     fields_named: &syn::FieldsNamed,
-) -> proc_macro2::TokenStream {
+) -> syn::Result<proc_macro2::TokenStream> {
     let fields = fields_named.named.clone();
 
     let struct_name = format!("{}", input.ident);
-    let attrs = LTVObjectAttrabutes::parse(&input);
+    let attrs = LTVObjectAttrabutes::parse(&input)?;
+
+    // `#[ltv(skip)]` fields are not on the wire
+    let mut skipped = Vec::new();
+    let mut tagged = Vec::new();
+    for f in fields {
+        let ltv_args = LtvAttributes::parse(&f.attrs)?;
+        if !ltv_args.skip {
+            tagged.push((f, ltv_args));
+            continue;
+        }
+        let is_tagged = f.attrs.iter().any(|a| {
+            a.path.is_ident("ltv_field") || a.path.is_ident("ltv_field_list") || a.path.is_ident("ltv_field_map")
+        });
+        let other_args = ltv_args.other
+            || ltv_args.default.is_some()
+            || ltv_args.skip_if.is_some()
+            || ltv_args.skip_if_default
+            || ltv_args.computed.is_some();
+        if is_tagged || other_args {
+            return Err(syn::Error::new_spanned(
+                &f.ident,
+                format!(
+                    "{}::{} can not use skip with ltv_field or other ltv arguments",
+                    struct_name,
+                    f.ident.as_ref().map(|i| i.to_string()).unwrap_or_default()
+                ),
+            ));
+        }
+        skipped.push(f.ident);
+    }
 
-    let ltv_fields: Vec<LtvFieldInfo> = fields
+    let mut used_ids = HashMap::new();
+    let mut ltv_fields: Vec<LtvFieldInfo> = tagged
         .into_iter()
-        .map(|f| {
+        .map(|(f, ltv_args)| -> syn::Result<LtvFieldInfo> {
             let ident_name = match &f.ident {
                 Some(i) => i.to_string(),
                 None => String::from("<No Name>"),
             };
             let full_name = format!("{}::{}", &struct_name, ident_name);
 
-            let (is_list, ltv_id): (bool, u8) = {
-                let ltv_id_attr = f
-                    .attrs
-                    .into_iter()
-                    .filter(|e| e.path.is_ident("ltv_field") || e.path.is_ident("ltv_field_list"))
-                    .next()
-                    .expect(&format!(
-                        "{} does not have ltv_field or ltv_field_list",
-                        &full_name
-                    ));
+            let ltv_id_attr = f
+                .attrs
+                .iter()
+                .filter(|e| {
+                    e.path.is_ident("ltv_field")
+                        || e.path.is_ident("ltv_field_list")
+                        || e.path.is_ident("ltv_field_map")
+                })
+                .next()
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &f.ident,
+                        format!("{} does not have ltv_field, ltv_field_list, ltv_field_map or ltv(skip)", full_name),
+                    )
+                })?;
+            let is_map = ltv_id_attr.path.is_ident("ltv_field_map");
+            let is_list = !is_map && (ltv_id_attr.path.is_ident("ltv_field_list") || attrs.many);
+            let field_args = LtvFieldAttributes::parse(ltv_id_attr)?;
+            attrs.check_field_id(&mut used_ids, field_args.ltv_id, &full_name, ltv_id_attr)?;
+
+            let map_ids = if is_map {
+                Some((field_args.key.unwrap_or(1), field_args.value.unwrap_or(2)))
+            } else if field_args.key.is_some() || field_args.value.is_some() {
+                return Err(syn::Error::new_spanned(
+                    ltv_id_attr,
+                    format!("{} can only use key and value with ltv_field_map", full_name),
+                ));
+            } else {
+                None
+            };
 
-                let lit_id_lit_args: LitInt = ltv_id_attr.parse_args().expect(&format!(
-                    "{} has invalid field id. Must be a number",
-                    &full_name
+            let ty = f.ty;
+            // `compact` on the object only applies to plain integer fields
+            let compact = field_args.compact.unwrap_or(
+                attrs.compact && !is_list && !field_args.varint && is_primitive_int(&ty),
+            );
+            let mut wire_tys = Vec::new();
+            if let Some(width) = field_args.width {
+                wire_tys.push(quote! { ::ltv::NarrowInt<#ty, #width> });
+            }
+            if compact {
+                wire_tys.push(quote! { ::ltv::Compact<#ty> });
+            }
+            if field_args.varint {
+                wire_tys.push(quote! { ::ltv::VarInt<#ty> });
+            }
+            if field_args.unit.is_some() || field_args.bits.is_some() || field_args.epoch.is_some() {
+                let unit = field_args.unit.unwrap_or(1_000);
+                let width = field_args.bits.unwrap_or(8);
+                let epoch = field_args.epoch.unwrap_or(0);
+                wire_tys.push(quote! { ::ltv::Time<#ty, #unit, #width, { #epoch }> });
+            }
+            if let Some(policy) = &field_args.flags {
+                wire_tys.push(quote! { ::ltv::BitFlags<#ty, ::ltv::#policy> });
+            }
+            if field_args.decimals.is_some() || field_args.repr.is_some() {
+                let decimals = field_args.decimals.unwrap_or(0);
+                let repr = field_args.repr.clone().unwrap_or_else(|| syn::parse_quote!(i32));
+                wire_tys.push(quote! { ::ltv::Fixed<#repr, #decimals, #ty> });
+            }
+            if wire_tys.len() > 1 {
+                return Err(syn::Error::new_spanned(
+                    ltv_id_attr,
+                    format!("{} can only use one of width, compact, varint, unit/bits/epoch, flags and scale/repr", full_name),
+                ));
+            }
+            if (is_list || is_map) && !wire_tys.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    ltv_id_attr,
+                    format!("{} can not use width, compact, varint, unit/bits/epoch, flags or scale/repr on a list or map", full_name),
+                ));
+            }
+            let wire_ty = wire_tys.pop();
+
+            if ltv_args.other {
+                return Err(syn::Error::new_spanned(
+                    &f.ident,
+                    format!("{} can not use other, it is for enum variants", full_name),
+                ));
+            }
+            let default = match &ltv_args.default {
+                Some(Some(path)) => Some(quote! { #path() }),
+                Some(None) => Some(quote! { <#ty as ::core::default::Default>::default() }),
+                None if ltv_args.skip_if_default => {
+                    Some(quote! { <#ty as ::core::default::Default>::default() })
+                }
+                None => None,
+            };
+            if (is_list || is_map) && ltv_args.computed.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &f.ident,
+                    format!("{} can not use computed on a list or map", full_name),
+                ));
+            }
+            if ltv_args.computed.is_some()
+                && (ltv_args.default.is_some() || ltv_args.skip_if.is_some() || ltv_args.skip_if_default)
+            {
+                return Err(syn::Error::new_spanned(
+                    &f.ident,
+                    format!("{} can not use default, skip_if or skip_if_default with computed", full_name),
+                ));
+            }
+            if (is_list || is_map) && default.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &f.ident,
+                    format!("{} can not use default on a list or map, missing ones are empty", full_name),
                 ));
-                (
-                    ltv_id_attr.path.is_ident("ltv_field_list"),
-                    lit_id_lit_args
-                        .base10_parse()
-                        .expect(&format!("{} has invalid field id.", &full_name)),
-                )
+            }
+            let ident = &f.ident;
+            let skip_if = match (&ltv_args.skip_if, ltv_args.skip_if_default) {
+                (Some(_), true) => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!("{} can only use one of skip_if and skip_if_default", full_name),
+                    ))
+                }
+                // the skipped field must decode again, so a missing one needs a fallback
+                (Some(_), false) if default.is_none() && !is_list && !is_map && !is_option(&ty) => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!(
+                            "{} needs default with skip_if, e.g. #[ltv(skip_if = \"...\", default)]",
+                            full_name
+                        ),
+                    ))
+                }
+                (Some(path), false) => Some(quote! { #path(&self.#ident) }),
+                (None, true) => {
+                    Some(quote! { self.#ident == <#ty as ::core::default::Default>::default() })
+                }
+                (None, false) => None,
             };
 
-            LtvFieldInfo {
-                ltv_id,
+            Ok(LtvFieldInfo {
+                ltv_id: field_args.ltv_id,
                 ident: f.ident,
-                ty: f.ty,
-                is_list: is_list || attrs.many,
-            }
+                ty,
+                is_list,
+                wire_ty,
+                map_ids,
+                default,
+                skip_if,
+                computed: ltv_args.computed,
+            })
         })
-        .collect();
+        .collect::<syn::Result<_>>()?;
+    if attrs.canonical {
+        ltv_fields.sort_by_key(|f| f.ltv_id);
+    }
     let st_name = &input.ident;
 
     let byte_order = match attrs.byte_order {
@@ -193,20 +654,59 @@ fn impl_ltv_named(
     };
 
     let field_length_size = attrs.field_length_size.unwrap_or(1) as usize;
+    let writer_new = attrs.writer_constructor();
 
     let from_ltv_fn = {
-        let ltv_fields = ltv_fields.iter().map(|LtvFieldInfo { ident, ty, ltv_id, is_list }| {
+        let computed_checks = ltv_fields.iter().filter_map(|LtvFieldInfo { ident, ltv_id, computed, .. }| {
+            let computed = computed.as_ref()?;
+            let name = format!("{}::{}", st_name, ident.as_ref()?);
+            Some(quote! {
+                if obj.#ident != #computed(&obj) {
+                    return Err(::ltv::LTVError::UnexpectedValue(
+                        #ltv_id,
+                        format!("{} does not match its computed value", #name),
+                    ));
+                }
+            })
+        }).collect::<Vec<_>>();
+
+        let ltv_fields = ltv_fields.iter().map(|LtvFieldInfo { ident, ty, ltv_id, is_list, wire_ty, map_ids, default, .. }| {
             let branch_err_name = format!("{}::{:?}", st_name, match ident{
                 Some(e) => format!("{}", e),
                 None => "<Unknown>".into()
             });
-            if *is_list {
+            if let Some((key_id, value_id)) = map_ids {
+                quote! {
+                    #ident: reader.get_map::<#ty>(#ltv_id, #key_id, #value_id).map_err(|e| ::ltv::LTVError::InnerParseError(
+                        e.into(),
+                        format!(#branch_err_name)
+                    ))?
+                }
+            }else if *is_list {
                 quote! {
                     #ident: reader.get_many::<<#ty as LTVItemMany<#byte_order>>::Item, _>(#ltv_id).map_err(|e| ::ltv::LTVError::InnerParseError(
                         e.into(),
                         format!(#branch_err_name)
                     ))?
                 }
+            }else if let Some(default) = default {
+                let (read_ty, unwrap_wire) = match wire_ty {
+                    Some(wire_ty) => (wire_ty.clone(), Some(quote! { .map(|v| v.map(|v| v.0)) })),
+                    None => (quote! { #ty }, None),
+                };
+                quote! {
+                    #ident: reader.get_item_optional::<#read_ty>(#ltv_id)#unwrap_wire.map_err(|e| ::ltv::LTVError::InnerParseError(
+                        e.into(),
+                        format!(#branch_err_name)
+                    ))?.unwrap_or_else(|| #default)
+                }
+            }else if let Some(wire_ty) = wire_ty {
+                quote! {
+                    #ident: reader.get_item::<#wire_ty>(#ltv_id).map(|v| v.0).map_err(|e| ::ltv::LTVError::InnerParseError(
+                        e.into(),
+                        format!(#branch_err_name)
+                    ))?
+                }
             }else{
                 quote! {
                     #ident: reader.get_item::<#ty>(#ltv_id).map_err(|e| ::ltv::LTVError::InnerParseError(
@@ -215,16 +715,30 @@ fn impl_ltv_named(
                     ))?
                 }
             }
-        });
+        }).collect::<Vec<_>>();
 
+        // `LTVBytesReader` has the same getters, so nested `Bytes` are sliced instead of copied
         quote! {
             fn from_ltv(field_id: u8, data: &[u8]) -> ::ltv::LTVResult<Self> {
                 let reader = ::ltv::LTVReader::<#byte_order, #field_length_size>::new(&data);
-                Ok(
-                    Self{
-                        #(#ltv_fields),*
-                    }
-                )
+                let obj = Self{
+                    #(#ltv_fields,)*
+                    #(#skipped: ::core::default::Default::default(),)*
+                };
+                #(#computed_checks)*
+                Ok(obj)
+            }
+
+            ::ltv::__with_bytes! {
+                fn from_ltv_bytes(field_id: u8, data: ::ltv::__private::Bytes) -> ::ltv::LTVResult<Self> {
+                    let reader = ::ltv::LTVBytesReader::<#byte_order, #field_length_size>::new(data);
+                    let obj = Self{
+                        #(#ltv_fields,)*
+                        #(#skipped: ::core::default::Default::default(),)*
+                    };
+                    #(#computed_checks)*
+                    Ok(obj)
+                }
             }
         }
     };
@@ -233,29 +747,62 @@ fn impl_ltv_named(
         let ltv_fields = ltv_fields.iter().map(
             |LtvFieldInfo {
                  ident,
-                 ty,
                  ltv_id,
                  is_list,
+                 wire_ty,
+                 map_ids,
+                 skip_if,
+                 computed,
+                 ..
              }| {
-                if *is_list {
+                let value = match computed {
+                    Some(computed) => quote! { #computed(self) },
+                    None => quote! { self.#ident },
+                };
+                let write = if let Some((key_id, value_id)) = map_ids {
                     quote! {
-                        for o in <#ty as LTVItemMany<#byte_order>>::get_items(&self.#ident){
-                            buffer.write_ltv(#ltv_id, o).ok();
-                        }
+                        buffer.write_ltv_map(#ltv_id, #key_id, #value_id, &self.#ident)?;
+                    }
+                } else if *is_list {
+                    quote! {
+                        buffer.write_ltv_list(#ltv_id, &self.#ident)?;
+                    }
+                } else if let Some(wire_ty) = wire_ty {
+                    quote! {
+                        buffer.write_ltv(#ltv_id, &<#wire_ty>::from(#value))?;
                     }
                 } else {
                     quote! {
-                        buffer.write_ltv(#ltv_id, &self.#ident).ok();
+                        buffer.write_ltv(#ltv_id, &#value)?;
                     }
+                };
+                match skip_if {
+                    Some(skip_if) => quote! {
+                        if !(#skip_if) {
+                            #write
+                        }
+                    },
+                    None => write,
                 }
             },
-        );
+        ).collect::<Vec<_>>();
 
         quote! {
             fn to_ltv(&self) -> Vec<u8>{
-                let mut buffer = LTVWriter::<_, #byte_order, #field_length_size>::new(Vec::new());
+                <Self as LTVItem<#byte_order>>::try_to_ltv(self)
+                    .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+            }
+
+            fn try_to_ltv(&self) -> ::ltv::LTVResult<Vec<u8>>{
+                let mut buffer = LTVWriter::<_, #byte_order, #field_length_size>::#writer_new(Vec::new());
+                #(#ltv_fields)*
+                Ok(buffer.into_inner())
+            }
+
+            fn encode_ltv(&self, buf: &mut [u8]) -> ::ltv::LTVResult<usize>{
+                let mut buffer = ::ltv::LTVSliceWriter::<#byte_order, #field_length_size>::#writer_new(buf);
                 #(#ltv_fields)*
-                buffer.into_inner()
+                buffer.finish()
             }
         }
     };
@@ -292,24 +839,27 @@ fn impl_ltv_named(
     )
     .ok();
     */
-    e
+    Ok(e)
 }
 
 fn impl_ltv_unnamed(
     input: &DeriveInput,
     fields_unnamed: &syn::FieldsUnnamed,
-) -> proc_macro2::TokenStream {
+) -> syn::Result<proc_macro2::TokenStream> {
     let fields = fields_unnamed.unnamed.clone();
-    let attrs = LTVObjectAttrabutes::parse(&input);
+    let attrs = LTVObjectAttrabutes::parse(&input)?;
     //let struct_name = format!("{}", input.ident);
 
     let field = {
         let mut field_iter = fields.iter();
         let single_item = field_iter
             .next()
-            .expect("Unnamed struct must have a inner type.");
-        if let Some(_) = field_iter.next() {
-            panic!("Unnamed struct must only have a single inner type.");
+            .ok_or_else(|| syn::Error::new_spanned(fields_unnamed, "Unnamed struct must have a inner type."))?;
+        if let Some(extra) = field_iter.next() {
+            return Err(syn::Error::new_spanned(
+                extra,
+                "Unnamed struct must only have a single inner type.",
+            ));
         }
         single_item
     };
@@ -330,6 +880,7 @@ fn impl_ltv_unnamed(
     };
 
     let field_length_size = attrs.field_length_size.unwrap_or(1) as usize;
+    let writer_new = attrs.writer_constructor();
 
     let obj_impl = {
         if let Some(obj_id) = attrs.object_id {
@@ -345,17 +896,28 @@ fn impl_ltv_unnamed(
     };
    
     if attrs.many {
-        let ltv_id = attrs.object_id.expect("Must have object ID with many");
+        let ltv_id = attrs.object_id.ok_or_else(|| {
+            syn::Error::new_spanned(&input.ident, "Must have object ID with many")
+        })?;
        
         let e = quote! {
             #[automatically_derived]
             #byte_order_impl LTVItem<#byte_order> for #struct_ident {
                 fn to_ltv(&self) -> Vec<u8>{
-                    let mut buffer = LTVWriter::<_, #byte_order, #field_length_size>::new(Vec::new());
-                    for o in <#field as LTVItemMany<#byte_order>>::get_items(&self.0){
-                        buffer.write_ltv(#ltv_id, o).ok();
-                    }
-                    buffer.into_inner()
+                    <Self as LTVItem<#byte_order>>::try_to_ltv(self)
+                        .expect("failed to encode LTV item, use `try_to_ltv` to handle the error")
+                }
+
+                fn try_to_ltv(&self) -> ::ltv::LTVResult<Vec<u8>>{
+                    let mut buffer = LTVWriter::<_, #byte_order, #field_length_size>::#writer_new(Vec::new());
+                    buffer.write_ltv_list(#ltv_id, &self.0)?;
+                    Ok(buffer.into_inner())
+                }
+
+                fn encode_ltv(&self, buf: &mut [u8]) -> ::ltv::LTVResult<usize>{
+                    let mut buffer = ::ltv::LTVSliceWriter::<#byte_order, #field_length_size>::#writer_new(buf);
+                    buffer.write_ltv_list(#ltv_id, &self.0)?;
+                    buffer.finish()
                 }
     
                 fn from_ltv(field_id: u8, data: &[u8]) -> ::ltv::LTVResult<Self> {
@@ -377,7 +939,7 @@ fn impl_ltv_unnamed(
             )
             .ok();
             */
-            e
+            Ok(e)
     }else{
         let struct_ident = &input.ident;
         let e = quote! {
@@ -386,10 +948,24 @@ fn impl_ltv_unnamed(
                 fn to_ltv(&self) -> Vec<u8>{
                     <#field as LTVItem<#byte_order>>::to_ltv(&self.0)
                 }
+
+                fn try_to_ltv(&self) -> ::ltv::LTVResult<Vec<u8>>{
+                    <#field as LTVItem<#byte_order>>::try_to_ltv(&self.0)
+                }
+
+                fn encode_ltv(&self, buf: &mut [u8]) -> ::ltv::LTVResult<usize>{
+                    <#field as LTVItem<#byte_order>>::encode_ltv(&self.0, buf)
+                }
     
                 fn from_ltv(field_id: u8, data: &[u8]) -> ::ltv::LTVResult<Self> {
                     Ok(Self(<#field as LTVItem<#byte_order>>::from_ltv(field_id, data)?))
                 }
+
+                ::ltv::__with_bytes! {
+                    fn from_ltv_bytes(field_id: u8, data: ::ltv::__private::Bytes) -> ::ltv::LTVResult<Self> {
+                        Ok(Self(<#field as LTVItem<#byte_order>>::from_ltv_bytes(field_id, data)?))
+                    }
+                }
             }
             #obj_impl
         };
@@ -401,12 +977,12 @@ fn impl_ltv_unnamed(
             )
             .ok();'
             */
-            e
+            Ok(e)
     }
  
 }
 
-pub fn impl_ltv(input: DeriveInput) -> proc_macro2::TokenStream {
+pub fn impl_ltv(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     match &input.data {
         Data::Struct(DataStruct {
             fields: Fields::Named(fields),
@@ -416,7 +992,11 @@ pub fn impl_ltv(input: DeriveInput) -> proc_macro2::TokenStream {
             fields: Fields::Unnamed(fields),
             ..
         }) => impl_ltv_unnamed(&input, fields),
-        _ => panic!("this derive macro only works on structs"),
+        Data::Enum(data) => crate::enums::impl_ltv_enum(&input, data),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "this derive macro only works on structs with fields and enums",
+        )),
     }
 }
 /*
@@ -138,6 +138,40 @@ mod tests {
         assert_eq!(s1, s2);
     }
 
+    #[derive(Debug, Ltv, Default, PartialEq, Eq)]
+    #[object(id = 12, length_size = 2, byte_order=LE, reserved(2, 4..=6))]
+    struct ReservedFields {
+        #[ltv_field(1)]
+        field1: u8,
+        #[ltv_field(3)]
+        field3: u8,
+        #[ltv_field(7)]
+        field7: u8,
+    }
+
+    #[derive(Debug, LtvCollection, PartialEq, Eq)]
+    #[object(byte_order=LE, length_size=2, reserved(1..=9, 11))]
+    enum ReservedObjects {
+        Object1(LTVObjectExample),
+        Object2(ReservedFields),
+    }
+
+    #[test]
+    fn reserved_ids() {
+        let fields = ReservedFields {
+            field1: 1,
+            field3: 3,
+            field7: 7,
+        };
+        let bytes = fields.to_ltv_object();
+        assert_eq!(bytes, vec![10, 0, 12, 2, 1, 1, 2, 3, 3, 2, 7, 7]);
+        assert_eq!(ReservedFields::from_ltv_object(&bytes).unwrap(), fields);
+
+        let objects = ReservedObjects::from_ltv_object(&bytes).unwrap();
+        assert_eq!(objects, ReservedObjects::Object2(fields));
+        assert_eq!(objects.to_ltv_object(), bytes);
+    }
+
     #[derive(Debug, Ltv, Default, PartialEq, Eq)]
     #[object(id = 10, length_size = 1)]
     struct LTVObjectUnnamed(u32);
@@ -222,4 +256,735 @@ mod tests {
            vec![1,2, 4]
         );
     }
+
+    #[derive(Debug, Default, PartialEq, Eq, Ltv)]
+    #[object(id = 2, length_size = 1)]
+    struct ItemWithBlob {
+        #[ltv_field(1)]
+        pub blob: Vec<u8>,
+    }
+
+    #[test]
+    fn item_encode_error() {
+        let obj = ItemWithBlob {
+            blob: vec![0; 300],
+        };
+
+        assert!(matches!(
+            <ItemWithBlob as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&obj),
+            Err(LTVError::LengthOverflow { field_id: 1, .. })
+        ));
+
+        let obj = ItemWithBlob {
+            blob: vec![0; 253],
+        };
+        assert!(matches!(
+            <ItemWithBlob as LTVObjectConvertable<{ ByteOrder::BE }, 1>>::try_to_ltv_object(&obj),
+            Err(LTVError::LengthOverflow { field_id: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn encode_into_slice() {
+        let obj = ExampleStruct {
+            field1: 0x69,
+            field2: [12, 34, 56],
+        };
+        let expected = <ExampleStruct as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+
+        let mut buf = [0u8; 16];
+        let length = <ExampleStruct as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&obj, &mut buf).unwrap();
+        assert_eq!(&buf[..length], &expected[..]);
+
+        let mut buf = [0u8; 4];
+        assert!(matches!(
+            <ExampleStruct as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&obj, &mut buf),
+            Err(LTVError::BufferTooSmall { required: 8, available: 4 })
+        ));
+    }
+
+    #[test]
+    fn encode_object_into_slice() {
+        let my_object = LTVObjectExample {
+            field1: 55,
+            field2: None,
+        };
+        let expected = my_object.to_ltv_object();
+
+        let mut buf = [0u8; 16];
+        let length = MyObjects::Object1(my_object).encode_ltv_object(&mut buf).unwrap();
+        assert_eq!(&buf[..length], &expected[..]);
+
+        let mut buf = [0u8; 3];
+        assert!(matches!(
+            MyObjects::Object1(LTVObjectExample::default()).encode_ltv_object(&mut buf),
+            Err(LTVError::BufferTooSmall { required: 7, available: 3 })
+        ));
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, Ltv)]
+    #[object(id = 3, canonical = true)]
+    struct CanonicalItem {
+        #[ltv_field(3)]
+        pub field3: u8,
+        #[ltv_field(1)]
+        pub field1: Option<Vec<u8>>,
+        #[ltv_field(2)]
+        pub field2: Option<u16>,
+    }
+
+    #[test]
+    fn canonical_item() {
+        let obj = CanonicalItem {
+            field3: 0x69,
+            field1: Some(vec![]),
+            field2: None,
+        };
+
+        let bytes = <CanonicalItem as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(bytes, vec![1, 1, 2, 3, 0x69]);
+        assert!(LTVReaderBE::<1>::is_canonical(&bytes));
+        assert_eq!(
+            obj,
+            <CanonicalItem as LTVItem<{ ByteOrder::BE }>>::from_ltv(3, &bytes).unwrap()
+        );
+
+        let mut buf = [0u8; 8];
+        let length = <CanonicalItem as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&obj, &mut buf).unwrap();
+        assert_eq!(&buf[..length], &bytes[..]);
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, Ltv)]
+    struct NarrowFields {
+        #[ltv_field(1, width = 3)]
+        pub field1: u32,
+        #[ltv_field(2, width = 2)]
+        pub field2: i64,
+    }
+
+    #[test]
+    fn narrow_fields() {
+        let obj = NarrowFields {
+            field1: 0x010203,
+            field2: -1,
+        };
+
+        let bytes = <NarrowFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(bytes, vec![4, 1, 0x01, 0x02, 0x03, 3, 2, 0xFF, 0xFF]);
+        assert_eq!(
+            obj,
+            <NarrowFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        let too_big = NarrowFields {
+            field1: 0x01000000,
+            field2: 0,
+        };
+        assert!(matches!(
+            <NarrowFields as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&too_big),
+            Err(LTVError::EncodeError(_))
+        ));
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, Ltv)]
+    #[object(compact = true)]
+    struct CompactFields {
+        #[ltv_field(1)]
+        pub field1: u32,
+        #[ltv_field(2)]
+        pub field2: i64,
+        #[ltv_field(3, compact = false)]
+        pub field3: u16,
+        #[ltv_field(4)]
+        pub field4: Option<u32>,
+    }
+
+    #[test]
+    fn compact_fields() {
+        let obj = CompactFields {
+            field1: 5,
+            field2: -2,
+            field3: 7,
+            field4: Some(9),
+        };
+
+        let bytes = <CompactFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(
+            bytes,
+            vec![2, 1, 5, 2, 2, 0xFE, 3, 3, 0, 7, 5, 4, 0, 0, 0, 9]
+        );
+        assert_eq!(
+            obj,
+            <CompactFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        let wide = vec![5, 1, 0, 0, 0, 5, 3, 2, 0xFF, 0xFE, 3, 3, 0, 7];
+        let decoded = <CompactFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &wide).unwrap();
+        assert_eq!(decoded.field1, 5);
+        assert_eq!(decoded.field2, -2);
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, Ltv)]
+    #[object(compact = true)]
+    struct VarIntFields {
+        #[ltv_field(1, varint = true)]
+        pub field1: u64,
+        #[ltv_field(2, varint = true)]
+        pub field2: i32,
+        #[ltv_field(3)]
+        pub field3: u32,
+    }
+
+    #[test]
+    fn varint_fields() {
+        let obj = VarIntFields {
+            field1: 300,
+            field2: -65,
+            field3: 0x0102,
+        };
+
+        let bytes = <VarIntFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(bytes, vec![3, 1, 0xAC, 0x02, 3, 2, 0x81, 0x01, 3, 3, 0x01, 0x02]);
+        assert_eq!(
+            obj,
+            <VarIntFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        assert!(<VarIntFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[2, 1, 0x80]).is_err());
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, Ltv)]
+    struct MapFields {
+        #[ltv_field_map(1)]
+        pub names: std::collections::BTreeMap<u16, String>,
+        #[ltv_field_map(2, key = 3, value = 4)]
+        pub flags: std::collections::HashMap<u8, bool>,
+    }
+
+    #[test]
+    fn map_fields() {
+        let mut obj = MapFields::default();
+        obj.names.insert(0x0102, String::from("x"));
+        obj.flags.insert(9, true);
+
+        let bytes = <MapFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(
+            bytes,
+            vec![8, 1, 3, 1, 0x01, 0x02, 2, 2, b'x', 7, 2, 2, 3, 9, 2, 4, 1]
+        );
+        assert_eq!(
+            obj,
+            <MapFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        let duplicate = [bytes.clone(), bytes[9..].to_vec()].concat();
+        assert!(<MapFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &duplicate).is_err());
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, Ltv)]
+    #[object(canonical = true)]
+    struct CanonicalMap {
+        #[ltv_field_map(1)]
+        pub flags: std::collections::HashMap<u8, bool>,
+    }
+
+    #[test]
+    fn canonical_hash_map() {
+        let mut obj = CanonicalMap::default();
+        for key in [7, 1, 200, 3, 42, 9, 0, 128] {
+            obj.flags.insert(key, key % 2 == 0);
+        }
+
+        let bytes = <CanonicalMap as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        let keys = bytes.chunks(8).map(|entry| entry[4]).collect::<Vec<_>>();
+        assert_eq!(keys, vec![0, 1, 3, 7, 9, 42, 128, 200]);
+        assert_eq!(
+            obj,
+            <CanonicalMap as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        let mut buf = [0u8; 64];
+        let length = <CanonicalMap as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&obj, &mut buf).unwrap();
+        assert_eq!(&buf[..length], &bytes[..]);
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, Ltv)]
+    #[object(canonical = true)]
+    struct CanonicalSet {
+        #[ltv_field_list(1)]
+        pub ids: std::collections::HashSet<u16>,
+    }
+
+    #[test]
+    fn canonical_hash_set() {
+        let obj = CanonicalSet {
+            ids: [0x0300, 7, 0x0101, 42, 0xFFFF, 0, 9, 0x0100].iter().copied().collect(),
+        };
+
+        let bytes = <CanonicalSet as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        let ids = bytes
+            .chunks(4)
+            .map(|item| u16::from_be_bytes([item[2], item[3]]))
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![0, 7, 9, 42, 0x0100, 0x0101, 0x0300, 0xFFFF]);
+        assert_eq!(
+            obj,
+            <CanonicalSet as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        let mut buf = [0u8; 64];
+        let length = <CanonicalSet as LTVItem<{ ByteOrder::BE }>>::encode_ltv(&obj, &mut buf).unwrap();
+        assert_eq!(&buf[..length], &bytes[..]);
+    }
+
+    #[derive(Debug, PartialEq, Eq, Ltv)]
+    struct Chunk {
+        #[ltv_field(1)]
+        pub data: bytes::Bytes,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Ltv)]
+    struct Frame {
+        #[ltv_field(1)]
+        pub id: u8,
+        #[ltv_field(2)]
+        pub chunk: Chunk,
+        #[ltv_field_list(3)]
+        pub extra: Vec<Chunk>,
+    }
+
+    #[test]
+    fn nested_bytes_are_not_copied() {
+        let frame = Frame {
+            id: 1,
+            chunk: Chunk { data: bytes::Bytes::from_static(b"abc") },
+            extra: vec![Chunk { data: bytes::Bytes::from_static(b"de") }],
+        };
+        let body = bytes::Bytes::from(<Frame as LTVItem<{ ByteOrder::BE }>>::to_ltv(&frame));
+        assert_eq!(body[..], [2, 1, 1, 6, 2, 4, 1, b'a', b'b', b'c', 5, 3, 3, 1, b'd', b'e'][..]);
+
+        let decoded = <Frame as LTVItem<{ ByteOrder::BE }>>::from_ltv_bytes(0, body.clone()).unwrap();
+        assert_eq!(decoded, frame);
+        // the nested values point into the original buffer
+        assert_eq!(decoded.chunk.data.as_ptr(), body[7..].as_ptr());
+        assert_eq!(decoded.extra[0].data.as_ptr(), body[14..].as_ptr());
+
+        let outer = [&[17, 9][..], &body[..]].concat();
+        let reader = LTVBytesReaderBE::<1>::new(bytes::Bytes::from(outer));
+        let decoded = reader.get_item::<Frame>(9).unwrap();
+        assert_eq!(decoded.chunk.data.as_ptr(), reader.into_inner()[9..].as_ptr());
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, Ltv)]
+    struct TreeNode {
+        #[ltv_field(1)]
+        pub value: u8,
+        #[ltv_field(2)]
+        pub left: Option<Box<TreeNode>>,
+        #[ltv_field(3)]
+        pub right: Option<Box<TreeNode>>,
+    }
+
+    #[test]
+    fn recursive_item() {
+        let tree = TreeNode {
+            value: 1,
+            left: Some(Box::new(TreeNode {
+                value: 2,
+                left: None,
+                right: Some(Box::new(TreeNode {
+                    value: 3,
+                    ..Default::default()
+                })),
+            })),
+            right: None,
+        };
+
+        let bytes = <TreeNode as LTVItem<{ ByteOrder::BE }>>::to_ltv(&tree);
+        assert_eq!(bytes, vec![2, 1, 1, 9, 2, 2, 1, 2, 4, 3, 2, 1, 3]);
+        assert_eq!(
+            tree,
+            <TreeNode as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+    }
+
+    #[derive(Debug, PartialEq, Eq, Ltv)]
+    struct TimeFields {
+        #[ltv_field(1, unit = "s", bits = 32)]
+        pub created: std::time::SystemTime,
+        #[ltv_field(2, unit = "us")]
+        pub timeout: std::time::Duration,
+        #[ltv_field(3, unit = "s", bits = 32, epoch = 946684800)]
+        pub updated: std::time::SystemTime,
+    }
+
+    #[test]
+    fn time_fields() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let obj = TimeFields {
+            created: UNIX_EPOCH + Duration::from_secs(0x01020304),
+            timeout: Duration::from_micros(5),
+            updated: UNIX_EPOCH + Duration::from_secs(946684800 + 2),
+        };
+
+        let bytes = <TimeFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(
+            bytes,
+            vec![5, 1, 1, 2, 3, 4, 9, 2, 0, 0, 0, 0, 0, 0, 0, 5, 5, 3, 0, 0, 0, 2]
+        );
+        assert_eq!(
+            obj,
+            <TimeFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+    }
+
+    bitflags::bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct Perms: u8 {
+            const READ = 0x01;
+            const WRITE = 0x02;
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Ltv)]
+    struct FlagFields {
+        #[ltv_field(1, flags = "reject")]
+        pub perms: Perms,
+        #[ltv_field(2, flags = "truncate")]
+        pub other: Perms,
+    }
+
+    #[test]
+    fn flag_fields() {
+        let obj = FlagFields {
+            perms: Perms::READ | Perms::WRITE,
+            other: Perms::WRITE,
+        };
+
+        let bytes = <FlagFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(bytes, vec![2, 1, 0x03, 2, 2, 0x02]);
+        assert_eq!(
+            obj,
+            <FlagFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        let unknown = <FlagFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[2, 1, 0x01, 2, 2, 0x82]).unwrap();
+        assert_eq!(unknown.other, Perms::WRITE);
+        assert!(<FlagFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[2, 1, 0x81, 2, 2, 0x02]).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Ltv)]
+    struct ScaledFields {
+        #[ltv_field(1, scale = 0.01, repr = i16)]
+        pub temperature: f64,
+        #[ltv_field(2, scale = 0.1)]
+        pub voltage: f32,
+    }
+
+    #[test]
+    fn scaled_fields() {
+        let obj = ScaledFields {
+            temperature: -21.37,
+            voltage: 3.3,
+        };
+
+        let bytes = <ScaledFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(bytes, vec![3, 1, 0xF7, 0xA7, 5, 2, 0, 0, 0, 33]);
+        assert_eq!(
+            obj,
+            <ScaledFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        let too_precise = ScaledFields {
+            temperature: 0.001,
+            voltage: 0.0,
+        };
+        assert!(<ScaledFields as LTVItem<{ ByteOrder::BE }>>::try_to_ltv(&too_precise).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Ltv)]
+    #[repr(u16)]
+    enum Mode {
+        Off,
+        On,
+        Auto = 10,
+        Eco,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Ltv)]
+    #[repr(u8)]
+    enum Channel {
+        Red = 1,
+        Green,
+        #[ltv(other)]
+        Unknown(u8),
+    }
+
+    #[derive(Debug, PartialEq, Eq, Ltv)]
+    struct EnumFields {
+        #[ltv_field(1)]
+        pub mode: Mode,
+        #[ltv_field(2)]
+        pub channel: Channel,
+    }
+
+    #[test]
+    fn c_like_enums() {
+        assert_eq!(<Mode as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Mode::Eco), vec![0, 11]);
+        assert_eq!(<Mode as LTVItem<{ ByteOrder::LE }>>::to_ltv(&Mode::On), vec![1, 0]);
+        assert_eq!(<Mode as LTVItem<{ ByteOrder::BE }>>::FIXED_SIZE, Some(2));
+        assert_eq!(<Mode as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0, 10]).unwrap(), Mode::Auto);
+        assert!(matches!(
+            <Mode as LTVItem<{ ByteOrder::BE }>>::from_ltv(4, &[0, 2]),
+            Err(LTVError::UnexpectedValue(4, _))
+        ));
+
+        let obj = EnumFields {
+            mode: Mode::Off,
+            channel: Channel::Green,
+        };
+        let bytes = <EnumFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(bytes, vec![3, 1, 0, 0, 2, 2, 2]);
+        assert_eq!(
+            obj,
+            <EnumFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        let unknown = <EnumFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[3, 1, 0, 1, 2, 2, 7]).unwrap();
+        assert_eq!(unknown.channel, Channel::Unknown(7));
+        assert_eq!(<Channel as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Channel::Unknown(7)), vec![7]);
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Ltv)]
+    #[repr(u32)]
+    enum Big {
+        A = 0x8000_0000,
+        B,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Ltv)]
+    #[repr(u64)]
+    enum Huge {
+        Low,
+        High = 1 << 63,
+        Max = u64::MAX,
+    }
+
+    #[test]
+    fn c_like_enums_high_bit() {
+        assert_eq!(<Big as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Big::A), vec![0x80, 0, 0, 0]);
+        assert_eq!(<Big as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Big::B), vec![0x80, 0, 0, 1]);
+        assert_eq!(<Big as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[0x80, 0, 0, 1]).unwrap(), Big::B);
+
+        assert_eq!(
+            <Huge as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Huge::High),
+            vec![0x80, 0, 0, 0, 0, 0, 0, 0]
+        );
+        for value in [Huge::Low, Huge::High, Huge::Max] {
+            let bytes = <Huge as LTVItem<{ ByteOrder::LE }>>::to_ltv(&value);
+            assert_eq!(<Huge as LTVItem<{ ByteOrder::LE }>>::from_ltv(0, &bytes).unwrap(), value);
+        }
+    }
+
+    #[derive(Debug, PartialEq, Ltv)]
+    enum Reading {
+        #[ltv_field(1)]
+        Temperature(i16),
+        #[ltv_field(2)]
+        Label(String),
+        #[ltv_field(3)]
+        Missing,
+    }
+
+    #[derive(Debug, PartialEq, Ltv)]
+    struct OneOfField {
+        #[ltv_field(1)]
+        pub id: u8,
+        #[ltv_field(2)]
+        pub reading: Reading,
+    }
+
+    #[test]
+    fn one_of_enums() {
+        let bytes = <Reading as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Reading::Temperature(-2));
+        assert_eq!(bytes, vec![3, 1, 0xFF, 0xFE]);
+        assert_eq!(
+            <Reading as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap(),
+            Reading::Temperature(-2)
+        );
+        assert_eq!(<Reading as LTVItem<{ ByteOrder::BE }>>::to_ltv(&Reading::Missing), vec![1, 3]);
+        assert_eq!(<Reading as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[1, 3]).unwrap(), Reading::Missing);
+
+        let obj = OneOfField {
+            id: 7,
+            reading: Reading::Label(String::new()),
+        };
+        let bytes = <OneOfField as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(bytes, vec![2, 1, 7, 3, 2, 1, 2]);
+        assert_eq!(
+            obj,
+            <OneOfField as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+
+        assert!(matches!(
+            <Reading as LTVItem<{ ByteOrder::BE }>>::from_ltv(5, &[2, 4, 0]),
+            Err(LTVError::OneOf { field_id: 5, found: 0 })
+        ));
+        assert!(matches!(
+            <Reading as LTVItem<{ ByteOrder::BE }>>::from_ltv(5, &[1, 3, 2, 2, b'a']),
+            Err(LTVError::OneOf { field_id: 5, found: 2 })
+        ));
+    }
+
+    fn default_retries() -> u8 {
+        3
+    }
+
+    fn is_empty(s: &str) -> bool {
+        s.is_empty()
+    }
+
+    #[derive(Debug, PartialEq, Eq, Ltv)]
+    struct DefaultFields {
+        #[ltv_field(1)]
+        #[ltv(default)]
+        pub flags: u16,
+        #[ltv_field(2)]
+        #[ltv(default = "default_retries")]
+        pub retries: u8,
+        #[ltv_field(3)]
+        #[ltv(skip_if = "is_empty", default)]
+        pub name: String,
+        #[ltv_field(4, width = 3)]
+        #[ltv(skip_if_default)]
+        pub offset: u32,
+    }
+
+    #[test]
+    fn default_fields() {
+        let obj = <DefaultFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[]).unwrap();
+        assert_eq!(
+            obj,
+            DefaultFields {
+                flags: 0,
+                retries: 3,
+                name: String::new(),
+                offset: 0,
+            }
+        );
+
+        let bytes = <DefaultFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(bytes, vec![3, 1, 0, 0, 2, 2, 3]);
+
+        let obj = DefaultFields {
+            flags: 1,
+            retries: 0,
+            name: "a".into(),
+            offset: 5,
+        };
+        let bytes = <DefaultFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert_eq!(bytes, vec![3, 1, 0, 1, 2, 2, 0, 2, 3, b'a', 4, 4, 0, 0, 5]);
+        assert_eq!(
+            obj,
+            <DefaultFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap()
+        );
+    }
+
+    fn is_zero(v: &Option<u8>) -> bool {
+        *v == Some(0)
+    }
+
+    #[derive(Debug, PartialEq, Eq, Ltv)]
+    struct SkipIfFields {
+        #[ltv_field(1)]
+        #[ltv(skip_if = "is_zero")]
+        pub level: Option<u8>,
+        #[ltv_field_list(2)]
+        #[ltv(skip_if = "Vec::is_empty")]
+        pub tags: Vec<u8>,
+    }
+
+    #[test]
+    fn skip_if_without_default() {
+        let obj = SkipIfFields {
+            level: Some(0),
+            tags: vec![],
+        };
+        let bytes = <SkipIfFields as LTVItem<{ ByteOrder::BE }>>::to_ltv(&obj);
+        assert!(bytes.is_empty());
+        // missing `Option`s and lists still decode
+        assert_eq!(
+            <SkipIfFields as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap(),
+            SkipIfFields {
+                level: None,
+                tags: vec![],
+            }
+        );
+    }
+
+    fn payload_length(msg: &Message) -> u8 {
+        msg.payload.len() as u8
+    }
+
+    #[derive(Debug, PartialEq, Eq, Ltv)]
+    struct Message {
+        #[ltv_field(1)]
+        #[ltv(computed = "payload_length")]
+        pub length: u8,
+        #[ltv_field(2)]
+        pub payload: Vec<u8>,
+        #[ltv(skip)]
+        pub received_at: Option<u64>,
+    }
+
+    #[test]
+    fn skip_and_computed_fields() {
+        let msg = Message {
+            length: 0,
+            payload: vec![7, 8],
+            received_at: Some(1234),
+        };
+
+        let bytes = <Message as LTVItem<{ ByteOrder::BE }>>::to_ltv(&msg);
+        assert_eq!(bytes, vec![2, 1, 2, 3, 2, 7, 8]);
+        assert_eq!(
+            <Message as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &bytes).unwrap(),
+            Message {
+                length: 2,
+                payload: vec![7, 8],
+                received_at: None,
+            }
+        );
+        assert!(matches!(
+            <Message as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[2, 1, 5, 3, 2, 7, 8]),
+            Err(LTVError::UnexpectedValue(1, _))
+        ));
+    }
+
+    #[test]
+    fn computed_field_mismatch() {
+        // the stored length is ignored when encoding
+        let msg = Message {
+            length: 9,
+            payload: vec![7, 8, 9],
+            received_at: None,
+        };
+        let bytes = <Message as LTVItem<{ ByteOrder::BE }>>::to_ltv(&msg);
+        assert_eq!(bytes, vec![2, 1, 3, 4, 2, 7, 8, 9]);
+
+        // a length that does not match the payload is rejected
+        match <Message as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[2, 1, 2, 4, 2, 7, 8, 9]) {
+            Err(LTVError::UnexpectedValue(1, message)) => {
+                assert_eq!(message, "Message::length does not match its computed value")
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        // the computed field is still required
+        assert!(matches!(
+            <Message as LTVItem<{ ByteOrder::BE }>>::from_ltv(0, &[4, 2, 7, 8, 9]),
+            Err(LTVError::InnerParseError(e, _)) if matches!(*e, LTVError::NotFound(1))
+        ));
+    }
 }
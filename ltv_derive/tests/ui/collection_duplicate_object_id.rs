@@ -0,0 +1,25 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+#[object(id = 3)]
+struct First {
+    #[ltv_field(1)]
+    a: u8,
+}
+
+#[derive(Ltv)]
+#[object(id = 3)]
+struct Second {
+    #[ltv_field(1)]
+    b: u8,
+}
+
+#[derive(LtvCollection)]
+#[object(byte_order = LE)]
+enum Objects {
+    First(First),
+    Second(Second),
+}
+
+fn main() {}
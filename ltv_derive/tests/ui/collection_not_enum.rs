@@ -0,0 +1,9 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(LtvCollection)]
+struct NotEnum {
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+#[object(id = 3)]
+struct First {
+    #[ltv_field(1)]
+    a: u8,
+}
+
+#[derive(LtvCollection)]
+#[object(byte_order = LE, reserved(0..=4))]
+enum Objects {
+    First(First),
+}
+
+fn main() {}
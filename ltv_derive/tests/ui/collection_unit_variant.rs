@@ -0,0 +1,9 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(LtvCollection)]
+enum Objects {
+    Nothing,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+enum Mode {
+    #[ltv("other")]
+    Off,
+}
+
+fn main() {}
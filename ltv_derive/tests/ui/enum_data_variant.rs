@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+enum Mode {
+    Off,
+    On(u8),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+enum Channel {
+    Red,
+    #[ltv(other)]
+    Unknown(String),
+}
+
+fn main() {}
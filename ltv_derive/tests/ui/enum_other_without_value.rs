@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+enum Mode {
+    Off,
+    #[ltv(other)]
+    Unknown,
+}
+
+fn main() {}
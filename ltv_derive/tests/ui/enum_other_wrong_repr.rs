@@ -0,0 +1,12 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+#[repr(u8)]
+enum Channel {
+    Red = 1,
+    #[ltv(other)]
+    Unknown(u16),
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+enum Mode {
+    Off,
+    #[ltv(other)]
+    Unknown(u8),
+    #[ltv(other)]
+    Other(u8),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct AdapterOnList {
+    #[ltv_field_list(1, width = 3)]
+    a: Vec<u32>,
+}
+
+fn main() {}
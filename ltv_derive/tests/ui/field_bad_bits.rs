@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct BadBits {
+    #[ltv_field(1, bits = 16)]
+    a: std::time::Duration,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct BadPath {
+    #[ltv_field(1)]
+    #[ltv(default = "not a path")]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct BadFlags {
+    #[ltv_field(1, flags = "ignore")]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct BadScale {
+    #[ltv_field(1, scale = 0.5)]
+    a: f64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct BadUnit {
+    #[ltv_field(1, unit = "minutes")]
+    a: std::time::Duration,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+fn items(_: &ComputedList) -> Vec<u8> {
+    Vec::new()
+}
+
+#[derive(Ltv)]
+struct ComputedList {
+    #[ltv_field_list(1)]
+    #[ltv(computed = "items")]
+    a: Vec<u8>,
+}
+
+fn main() {}
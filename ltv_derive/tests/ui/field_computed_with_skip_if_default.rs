@@ -0,0 +1,19 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+fn sum(totals: &Totals) -> u8 {
+    totals.a + totals.b
+}
+
+#[derive(Ltv)]
+struct Totals {
+    #[ltv_field(1)]
+    a: u8,
+    #[ltv_field(2)]
+    b: u8,
+    #[ltv_field(3)]
+    #[ltv(computed = "sum", skip_if_default)]
+    total: u8,
+}
+
+fn main() {}
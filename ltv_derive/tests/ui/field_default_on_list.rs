@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct DefaultList {
+    #[ltv_field_list(1)]
+    #[ltv(default)]
+    a: Vec<u8>,
+}
+
+fn main() {}
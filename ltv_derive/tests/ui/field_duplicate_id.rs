@@ -0,0 +1,12 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct Duplicate {
+    #[ltv_field(1)]
+    a: u8,
+    #[ltv_field(1)]
+    b: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct KeyOutsideMap {
+    #[ltv_field(1, key = 3)]
+    a: u8,
+}
+
+fn main() {}
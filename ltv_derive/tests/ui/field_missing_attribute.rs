@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct Missing {
+    #[ltv_field(1)]
+    a: u8,
+    b: u8,
+}
+
+fn main() {}
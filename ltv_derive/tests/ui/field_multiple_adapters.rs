@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct TwoAdapters {
+    #[ltv_field(1, width = 3, varint = true)]
+    a: u32,
+}
+
+fn main() {}
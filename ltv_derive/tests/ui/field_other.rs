@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct Other {
+    #[ltv_field(1)]
+    #[ltv(other)]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+#[object(reserved(5, 9..=12))]
+struct Reserved {
+    #[ltv_field(1)]
+    a: u8,
+    #[ltv_field(10)]
+    b: u8,
+}
+
+fn main() {}
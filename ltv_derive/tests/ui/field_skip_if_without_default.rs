@@ -0,0 +1,15 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+fn is_zero(v: &u8) -> bool {
+    *v == 0
+}
+
+#[derive(Ltv)]
+struct SkipIf {
+    #[ltv_field(1)]
+    #[ltv(skip_if = "is_zero")]
+    a: u8,
+}
+
+fn main() {}
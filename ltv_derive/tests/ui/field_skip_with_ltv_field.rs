@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct SkipTagged {
+    #[ltv_field(1)]
+    #[ltv(skip)]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct Unknown {
+    #[ltv_field(1, size = 3)]
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct Unknown {
+    #[ltv_field(1)]
+    #[ltv(optional)]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+#[object(byte_order = MIDDLE)]
+struct ByteOrder {
+    #[ltv_field(1)]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+#[object(id = 1, id = 2)]
+struct Duplicate {
+    #[ltv_field(1)]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+#[object(reserved(5, 12..=9))]
+struct Reserved {
+    #[ltv_field(1)]
+    a: u8,
+}
+
+fn main() {}
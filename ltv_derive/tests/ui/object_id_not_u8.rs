@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+#[object(id = 300)]
+struct BigId {
+    #[ltv_field(1)]
+    a: u8,
+}
+
+fn main() {}
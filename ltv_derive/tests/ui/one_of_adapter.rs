@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+enum Reading {
+    #[ltv_field(1, width = 3)]
+    Temperature(i32),
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+enum Reading {
+    #[ltv_field(1)]
+    Temperature(i16),
+    #[ltv_field(1)]
+    Label(String),
+}
+
+fn main() {}
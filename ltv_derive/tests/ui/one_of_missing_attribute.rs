@@ -0,0 +1,11 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+enum Reading {
+    #[ltv_field(1)]
+    Temperature(i16),
+    Label(String),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+enum Reading {
+    #[ltv_field(1)]
+    Temperature { value: i16 },
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+#[object(many = true)]
+struct Many(Vec<u8>);
+
+fn main() {}
@@ -0,0 +1,7 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct Empty();
+
+fn main() {}
@@ -0,0 +1,7 @@
+#![feature(adt_const_params)]
+use ltv::*;
+
+#[derive(Ltv)]
+struct Two(u8, u16);
+
+fn main() {}